@@ -0,0 +1,442 @@
+use proc_macro2::TokenStream;
+use syn::{*, parse::{Parse, ParseStream}};
+use quote::quote;
+use crate::{field_attributes::FieldAttributes, output::Output, root_attributes::RootAttributes, utils::{is_type, get_vec_item_type, get_syntax_bounds}};
+use proc_macro_error::emit_error;
+
+struct Wrapper {
+    field: Field
+}
+
+impl Parse for Wrapper {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let field = Field::parse_named(input)?;
+
+        Ok(Self { field })
+    }
+}
+
+pub fn create_location_field(field_name: &str) -> Field {
+    let string = format!("pub {}: parsable::ItemLocation", field_name);
+    let result : Result<Wrapper> = syn::parse_str(&string);
+
+    result.unwrap().field
+}
+
+pub fn process_struct(data_struct: &mut DataStruct, root_attributes: &mut RootAttributes, output: &mut Output) {
+    output.get_location = quote! {
+        fn location(&self) -> &parsable::ItemLocation {
+            &self.location
+        }
+    };
+
+    let (root_markers_on_start, root_markers_on_exit, root_markers_on_fail) = root_attributes.get_push_pop_markers();
+    let mut markers_on_fail = vec![root_markers_on_fail];
+
+    match &mut data_struct.fields {
+        Fields::Named(named_fields) => {
+            let field_count = named_fields.named.len();
+            let mut field_names = vec![];
+            let mut lines = vec![];
+            let mut node_children = vec![];
+            let mut visited_fields = vec![];
+            let mut syntax_lines = vec![];
+            let mut cloned_fields = vec![];
+
+            for (i, field) in named_fields.named.iter_mut().enumerate() {
+                let attributes = FieldAttributes::from_field_attributes(&mut field.attrs);
+                let (field_markers_on_start, field_markers_on_exit, field_markers_on_fail) = attributes.get_push_pop_markers(i);
+                let is_vec = is_type(&field.ty, "Vec");
+                let is_option = is_type(&field.ty, "Option");
+
+                let field_name = field.ident.as_ref().unwrap();
+                let field_type = &field.ty;
+
+                field_names.push(quote! { #field_name });
+                markers_on_fail.insert(0, field_markers_on_fail);
+
+                let optional = is_option || attributes.optional.unwrap_or(false);
+                let is_string = attributes.regex.is_some() || attributes.value.is_some();
+
+                if !attributes.ignore {
+                    syntax_lines.push(get_field_syntax(field_name, &attributes, is_vec, optional));
+                    output.syntax_bounds.extend(get_syntax_bounds(field_type, is_vec && attributes.separator.is_some()));
+                }
+                let participate_in_cascade = root_attributes.cascade && attributes.cascade.unwrap_or(true);
+                let consume_spaces = match attributes.consume_spaces {
+                    Some(false) => quote! {},
+                    _ => quote! { reader__.eat_spaces(); }
+                };
+                let mut handle_failure = quote! {};
+                let mut on_fail = quote ! {
+                    reader__.set_index(start_index__);
+                    #(#markers_on_fail)*
+                    #root_markers_on_exit
+                    return None;
+                };
+
+                if optional {
+                    let set_option_failed = match participate_in_cascade {
+                        true => quote! { option_failed__ = true },
+                        false => quote! {},
+                    };
+
+                    on_fail = quote! {
+                        field_failed__ = true;
+                        #set_option_failed;
+                        reader__.set_index(field_index__);
+                        <#field_type as Default>::default()
+                    };
+
+                    if attributes.suffix.is_some() {
+                        handle_failure = quote! {
+                            if field_failed__ {
+                                #field_name = <#field_type as Default>::default();
+                            }
+                        }
+                    }
+                }
+
+                let mut check = vec![];
+                let has_prefix = attributes.prefix.is_some();
+                let has_suffix = attributes.suffix.is_some();
+
+                let mut pre_parsing_check = quote! {};
+
+                if optional && participate_in_cascade {
+                    pre_parsing_check = quote! {
+                        if option_failed__ {
+                            field_failed__ = true;
+                        }
+                    };
+                }
+
+                let prefix_parsing = match attributes.prefix {
+                    Some(prefix) => {
+                        let prefix_consume_spaces = match attributes.consume_spaces_after_prefix {
+                            Some(false) => quote! { {} },
+                            _ => quote! { reader__.eat_spaces() },
+                        };
+
+                        quote! {
+                            if !field_failed__ {
+                                match reader__.read_string(#prefix) {
+                                    Some(_) => #prefix_consume_spaces,
+                                    None => {
+                                        reader__.set_expected_string(#prefix);
+                                        prefix_ok__ = false;
+                                        field_failed__ = true;
+                                        #on_fail;
+                                    }
+                                };
+                            }
+                        }
+                    },
+                    None => quote! {}
+                };
+                let suffix_parsing = match attributes.suffix {
+                    Some(suffix) => {
+                        let suffix_consume_spaces = match attributes.consume_spaces_after_suffix {
+                            Some(false) => quote! { {} },
+                            _ => quote! { reader__.eat_spaces() },
+                        };
+
+                        quote! {
+                            if !field_failed__ {
+                                match reader__.read_string(#suffix) {
+                                    Some(_) => #suffix_consume_spaces,
+                                    None => {
+                                        reader__.set_expected_string(#suffix);
+                                        #on_fail;
+                                    }
+                                };
+                            }
+                        }
+                    },
+                    None => quote! {}
+                };
+
+                let mut exclude_parsing = quote! {};
+
+                if let Some(exclude) = &attributes.exclude {
+                    exclude_parsing = quote! {
+                        if !field_failed__ && reader__.peek_regex(#exclude) {
+                            #on_fail;
+                        }
+                    };
+                }
+
+                let mut followed_by_parsing = quote! {};
+
+                if let Some(followed_by) = &attributes.followed_by {
+                    followed_by_parsing = quote! {
+                        if !field_failed__ && !reader__.peek_regex(#followed_by) {
+                            reader__.set_expected_regex(#followed_by);
+                            #on_fail;
+                        }
+                    };
+                } else if let Some(not_followed_by) = &attributes.not_followed_by {
+                    followed_by_parsing = quote! {
+                        if !field_failed__ && reader__.peek_regex(#not_followed_by) {
+                            // reader__.set_expected_regex(#not_followed_by);
+                            #on_fail;
+                        }
+                    };
+                }
+
+                let mut parse_method = quote! { parse::<#field_type>() };
+                let has_recovery_tokens = !attributes.recover_terminators.is_empty() || !attributes.recover_delimiters.is_empty();
+
+                if has_recovery_tokens && !get_vec_item_type(field_type).is_some_and(|item_type| is_type(item_type, "Recoverable")) {
+                    emit_error!(field_type, "recover_terminators and recover_delimiters can only be set on `Vec<Recoverable<T>>` fields");
+                }
+
+                if is_vec {
+                    if has_recovery_tokens {
+                        let separator = match &attributes.separator {
+                            Some(separator) => quote! { Some(#separator) },
+                            None => quote! { None },
+                        };
+                        let terminators = &attributes.recover_terminators;
+                        let delimiters = &attributes.recover_delimiters;
+
+                        parse_method = quote! {
+                            parse_with_recovery_tokens::<#field_type>(#separator, parsable::RecoveryTokens {
+                                terminators: &[ #(#terminators),* ],
+                                delimiters: &[ #(#delimiters),* ]
+                            })
+                        };
+                    } else if let Some(separator) = attributes.separator {
+                        parse_method = quote! { parse_with_separator::<#field_type>(#separator) };
+                    } else if let Some(false) = attributes.consume_spaces_between_items {
+                        parse_method = quote! { parse_without_consuming_spaces::<#field_type>() };
+                    }
+                }
+
+                let mut assignment = quote! {
+                    let mut #field_name = match reader__.#parse_method {
+                        Some(value) => value,
+                        None => {
+                            reader__.set_expected_item::<#field_type>();
+                            #on_fail
+                        }
+                    };
+                };
+
+                if (has_prefix || participate_in_cascade) && optional {
+                    assignment = quote! {
+                        let mut #field_name = match prefix_ok__ && !option_failed__ {
+                            true => match reader__.#parse_method {
+                                Some(value) => value,
+                                None => {
+                                    reader__.set_expected_item::<#field_type>();
+                                    #on_fail
+                                }
+                            },
+                            false => <#field_type as Default>::default()
+                        };
+                    };
+
+                    // assignment = quote! {
+                    //     let #field_name = <#field_type as Default>::default();
+                    // };
+                }
+
+                let make_field_from_string = match is_option {
+                    true => quote! { Some(value) },
+                    false => quote! { value },
+                };
+
+                if let Some(pattern) = attributes.regex {
+                    assignment = quote! {
+                        let #field_name = match reader__.read_regex(#pattern) {
+                            Some(value) => match <String as std::str::FromStr>::from_str(value) {
+                                Ok(value) => #make_field_from_string,
+                                Err(_) => { #on_fail }
+                            },
+                            None => { #on_fail }
+                        };
+                    };
+                } else if let Some(literal) = attributes.value {
+                    assignment = quote! {
+                        let #field_name = match reader__.read_string(#literal) {
+                            Some(value) => match <String as std::str::FromStr>::from_str(value) {
+                                Ok(value) => #make_field_from_string,
+                                Err(_) => { #on_fail }
+                            },
+                            None => { #on_fail }
+                        };
+                    };
+
+                    if field_count == 1 && root_attributes.token.is_none() {
+                        root_attributes.token = Some(literal.clone());
+                    }
+                }
+
+                if let Some(min) = attributes.min {
+                    check.push(quote! {
+                        if !field_failed__ && #field_name.len() < #min {
+                            reader__.set_expected_item::<#field_type>();
+                            #on_fail;
+                        }
+                    });
+                }
+
+                if is_option && has_prefix {
+                    check.push(quote! {
+                        if #field_name.is_none() {
+                            #on_fail;
+                        }
+                    });
+                }
+
+                if is_vec && has_prefix && !has_suffix {
+                    check.push(quote! {
+                        if #field_name.is_empty() && prefix_ok__ {
+                            reader__.set_expected_item::<#field_type>();
+                            #on_fail;
+                        }
+                    });
+                }
+
+                // Ignored fields are always default while parsing, which is the only time items are cloned
+                cloned_fields.push(match (attributes.ignore, is_string) {
+                    (true, _) => quote! { #field_name: <#field_type as Default>::default() },
+                    (false, true) => quote! { #field_name: self.#field_name.clone() },
+                    (false, false) => quote! { #field_name: parsable::Parsable::clone_item(&self.#field_name)? },
+                });
+
+                if attributes.ignore {
+                    lines.push(quote! {
+                        let #field_name = <#field_type as Default>::default();
+                    });
+                } else {
+                    node_children.push(quote! {
+                        (&parsable::NodeField(&self.#field_name)).collect_field(children__);
+                    });
+                    visited_fields.push(field_name.clone());
+                    lines.push(quote! {
+                        #field_markers_on_start
+                        field_failed__ = false;
+                        prefix_ok__ = true;
+                        field_index__ = reader__.get_index();
+                        #pre_parsing_check
+                        #prefix_parsing
+                        #exclude_parsing
+                        #assignment
+                        #(#check)*
+                        #consume_spaces
+                        #suffix_parsing
+                        #followed_by_parsing
+                        #handle_failure
+                        #field_markers_on_exit
+                    });
+                }
+            }
+
+            let mut set_location = quote! {};
+            let mut node_location = quote! { None };
+
+            if root_attributes.located {
+                node_location = quote! { Some(&self.location) };
+                output.get_format_location = quote! {
+                    fn get_format_location(&self) -> Option<&parsable::ItemLocation> {
+                        Some(&self.location)
+                    }
+                };
+                field_names.push(quote! { location });
+                cloned_fields.push(quote! { location: self.location.clone() });
+                named_fields.named.insert(0, create_location_field("location"));
+                set_location = quote! { let location = reader__.get_item_location(start_index__); };
+            }
+
+            output.parse_item = quote! {
+                fn parse_item(reader__: &mut parsable::StringReader) -> Option<Self> {
+                    let start_index__ = reader__.get_index();
+                    let mut field_index__ : usize = 0;
+                    let mut field_failed__ = false;
+                    let mut prefix_ok__ = true;
+                    let mut option_failed__ = false;
+                    #root_markers_on_start
+                    #(#lines)*
+                    #root_markers_on_exit
+                    #set_location
+                    Some(Self { #(#field_names),* })
+                }
+            };
+
+            output.clone_item = quote! {
+                fn clone_item(&self) -> Option<Self> {
+                    Some(Self { #(#cloned_fields),* })
+                }
+            };
+
+            output.impl_node = quote! {
+                fn get_node_name(&self) -> String {
+                    <Self as parsable::Parsable>::get_item_name()
+                }
+
+                fn get_node_location(&self) -> Option<&parsable::ItemLocation> {
+                    #node_location
+                }
+
+                #[allow(unused_variables)]
+                fn collect_children<'a>(&'a self, children__: &mut Vec<&'a dyn parsable::Node>) {
+                    use parsable::{NodeFieldImpl as _, NodeFieldFallback as _};
+
+                    #(#node_children)*
+                }
+            };
+
+            output.write_syntax = quote! {
+                #(#syntax_lines)*
+            };
+
+            output.impl_visit = quote! {
+                #[allow(unused_variables)]
+                fn visit_children(&self, visitor__: &mut parsable::Visitor) {
+                    use parsable::{VisitFieldImpl as _, VisitFieldFallback as _};
+
+                    #((&parsable::VisitField(&self.#visited_fields)).visit_field(visitor__);)*
+                }
+            };
+
+            output.impl_visit_mut = quote! {
+                #[allow(unused_variables)]
+                fn visit_children_mut(&mut self, visitor__: &mut parsable::VisitorMut) {
+                    use parsable::{VisitFieldMutImpl as _, VisitFieldMutFallback as _};
+
+                    #((&mut parsable::VisitFieldMut(&mut self.#visited_fields)).visit_field_mut(visitor__);)*
+                }
+            };
+
+        },
+        Fields::Unnamed(_) => unreachable!(),
+        Fields::Unit => unreachable!()
+    }
+}
+
+// Prefix, content and suffix of a field, which are skipped if the field is optional and missing
+fn get_field_syntax(field_name: &Ident, attributes: &FieldAttributes, is_vec: bool, optional: bool) -> TokenStream {
+    let prefix = attributes.prefix.as_ref().map(|prefix| quote! { writer__.literal(#prefix); });
+    let suffix = attributes.suffix.as_ref().map(|suffix| quote! { writer__.literal(#suffix); });
+    let content = match (&attributes.separator, is_vec) {
+        (Some(separator), true) => quote! { writer__.separated(&self.#field_name, #separator); },
+        _ => quote! { writer__.item(&self.#field_name); },
+    };
+
+    match optional {
+        true => quote! {
+            if !writer__.is_empty(&self.#field_name) {
+                #prefix
+                #content
+                #suffix
+            }
+        },
+        false => quote! {
+            #prefix
+            #content
+            #suffix
+        },
+    }
+}
\ No newline at end of file
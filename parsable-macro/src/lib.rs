@@ -0,0 +1,202 @@
+#![allow(unused_assignments)]
+
+mod root_attributes;
+mod field_attributes;
+mod utils;
+mod impl_struct;
+mod impl_enum;
+mod output;
+mod markers;
+
+use proc_macro::{TokenStream};
+use proc_macro2::{Span};
+use quote::quote;
+use syn::*;
+use proc_macro_error::*;
+
+use root_attributes::*;
+use impl_struct::*;
+use impl_enum::*;
+
+use crate::output::Output;
+
+// https://docs.rs/syn/latest/syn/struct.DeriveInput.html
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn parsable(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut root_attributes = match syn::parse::<RootAttributes>(attr.clone()) {
+        Ok(attributes) => attributes,
+        Err(error) => {
+            emit_call_site_error!(error);
+            RootAttributes::default()
+        }
+    };
+    let mut ast : DeriveInput = syn::parse(input).unwrap();
+    let mut output = Output::default();
+    let name = &ast.ident;
+
+    let derive_attribute = syn::Attribute {
+        pound_token: Token![#](Span::call_site()),
+        style: AttrStyle::Outer,
+        bracket_token: syn::token::Bracket { span: Span::call_site() },
+        path: syn::parse_str("derive").unwrap(),
+        tokens: syn::parse_str("(Debug)").unwrap(),
+    };
+
+    // if let Data::Enum(_) = ast.data {
+    //     derive_attribute.tokens = syn::parse_str("(Debug, Clone, Copy, PartialEq)").unwrap();
+    // }
+
+    ast.attrs.push(derive_attribute);
+
+    match &mut ast.data {
+        Data::Struct(data) => process_struct(data, &mut root_attributes, &mut output),
+        Data::Enum(data) => process_enum(data, &root_attributes, &mut output),
+        Data::Union(_) => emit_call_site_error!("unions are not supported")
+    }
+
+    let impl_display = match output.display {
+        Some(body) => quote! {
+            impl std::fmt::Display for #name {
+                #body
+            }
+        },
+        None => quote! {},
+    };
+
+    let impl_as_str = match output.as_str {
+        Some(body) => quote! {
+            impl #name {
+                #body
+            }
+        },
+        None => quote! {}
+    };
+
+    let token_name = match &root_attributes.name {
+        Some(specified_name) => specified_name.to_string(),
+        None => match &root_attributes.token {
+            Some(token) => format!("\"{}\"", token),
+            None => name.to_string(),
+        }
+    };
+    let token_name_lit = LitStr::new(&token_name, Span::call_site());
+
+    let impl_item_name = quote! {
+        fn get_item_name() -> String {
+            #token_name_lit.to_string()
+        }
+    };
+
+    let get_location = output.get_location;
+    let parse_item = output.parse_item;
+    let clone_item = output.clone_item;
+    let get_completion_suggestions = output.get_completion_suggestions.unwrap_or_default();
+    let impl_node = output.impl_node;
+    let impl_visit = output.impl_visit;
+    let impl_visit_mut = output.impl_visit_mut;
+    let write_syntax = output.write_syntax;
+    let syntax_bounds = output.syntax_bounds;
+    let get_format_location = output.get_format_location;
+
+    // Implemented for the writers that can write all the fields, so that `ToSource` does not require `Format` and conversely.
+    // Always implemented, as manual implementations of `ToSource` and `Format` can use it.
+    let impl_syntax = quote! {
+        impl<W__ : parsable::SyntaxWriter> parsable::Syntax<W__> for #name where #(#syntax_bounds),* {
+            fn write_syntax(&self, writer__: &mut W__) {
+                #write_syntax
+            }
+        }
+    };
+
+    let impl_to_source = match root_attributes.impl_to_source {
+        true => quote! {
+            impl parsable::ToSource for #name {
+                fn write_source(&self, writer: &mut parsable::SourceWriter) {
+                    parsable::Syntax::write_syntax(self, writer);
+                }
+            }
+        },
+        false => quote! {},
+    };
+
+    let impl_format = match root_attributes.impl_format {
+        true => quote! {
+            impl parsable::Format for #name {
+                fn format(&self, formatter: &mut parsable::Formatter) -> parsable::Doc {
+                    formatter.format_syntax(self)
+                }
+
+                #get_format_location
+            }
+        },
+        false => quote! {},
+    };
+
+    let recover_terminators = &root_attributes.recover_terminators;
+    let recover_delimiters = &root_attributes.recover_delimiters;
+    let impl_recover = match recover_terminators.is_empty() && recover_delimiters.is_empty() {
+        true => quote! {},
+        false => quote! {
+            impl parsable::Recover for #name {
+                fn get_recovery_terminators() -> &'static[&'static str] {
+                    &[ #(#recover_terminators),* ]
+                }
+
+                fn get_recovery_delimiters() -> &'static[&'static str] {
+                    &[ #(#recover_delimiters),* ]
+                }
+            }
+        },
+    };
+
+    let result = quote! {
+        #ast
+
+        impl parsable::Parsable for #name {
+            #parse_item
+
+            #impl_item_name
+
+            #get_location
+
+            #get_completion_suggestions
+
+            #clone_item
+        }
+
+        impl parsable::Node for #name {
+            #impl_node
+        }
+
+        impl parsable::Visit for #name {
+            #impl_visit
+        }
+
+        impl parsable::VisitMut for #name {
+            #impl_visit_mut
+        }
+
+        #impl_recover
+
+        #impl_syntax
+
+        #impl_to_source
+
+        #impl_format
+
+        impl std::ops::Deref for #name {
+            type Target = parsable::ItemLocation;
+
+            fn deref(&self) -> &parsable::ItemLocation {
+                <Self as parsable::Parsable>::location(self)
+            }
+        }
+
+        #impl_display
+
+        #impl_as_str
+    };
+
+    result.into()
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+use proc_macro2::{TokenStream};
+
+#[derive(Default)]
+pub struct Output {
+    pub display: Option<TokenStream>,
+    pub as_str: Option<TokenStream>,
+    pub get_location: TokenStream,
+    pub parse_item: TokenStream,
+    pub clone_item: TokenStream,
+    pub get_completion_suggestions: Option<TokenStream>,
+    pub impl_node: TokenStream,
+    pub impl_visit: TokenStream,
+    pub impl_visit_mut: TokenStream,
+    pub write_syntax: TokenStream,
+    pub syntax_bounds: Vec<TokenStream>,
+    pub get_format_location: TokenStream
+}
\ No newline at end of file
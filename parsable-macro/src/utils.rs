@@ -0,0 +1,53 @@
+use proc_macro2::{Span, TokenStream};
+use syn::{*, parse::ParseStream, punctuated::Punctuated};
+use quote::quote;
+
+pub fn is_type(ty: &Type, name: &str) -> bool {
+    get_type_name(ty) == name
+}
+
+fn get_type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last().unwrap().ident.to_string(),
+        _ => String::new(),
+    }
+}
+
+// Type of the items of a `Vec<T>`
+pub fn get_vec_item_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else { return None };
+    let PathArguments::AngleBracketed(arguments) = &type_path.path.segments.last()?.arguments else { return None };
+
+    match arguments.args.first()? {
+        GenericArgument::Type(item_type) => Some(item_type),
+        _ => None
+    }
+}
+
+// Bounds of the `Syntax` implementation for a field: the writer must be able to write it, and its items if they are separated
+pub fn get_syntax_bounds(ty: &Type, separated: bool) -> Vec<TokenStream> {
+    let mut bounds = vec![quote! { #ty : parsable::SyntaxItem<W__> }];
+
+    if let Some(item_type) = get_vec_item_type(ty).filter(|_| separated) {
+        bounds.push(quote! { #item_type : parsable::SyntaxItem<W__> });
+    }
+
+    bounds
+}
+
+// A string, or a list of strings between brackets
+pub fn parse_string_list(content: ParseStream) -> syn::Result<Vec<LitStr>> {
+    if !content.peek(token::Bracket) {
+        return Ok(vec![content.parse::<LitStr>()?]);
+    }
+
+    let list;
+
+    bracketed!(list in content);
+
+    Ok(Punctuated::<LitStr, Token![,]>::parse_terminated(&list)?.into_iter().collect())
+}
+
+pub fn make_ident(name: String) -> Ident {
+    Ident::new(&name, Span::call_site())
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use unicode_xid::UnicodeXID;
+use crate::{Parsable, ParseOptions, StringReader, FileInfo, SharedFileInfo, ItemLocation};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    Literal,
+    Suggestion
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionItem {
+    pub label: String,
+    pub kind: CompletionKind,
+    pub item_name: Option<String>
+}
+
+#[derive(Debug)]
+pub struct Completion {
+    pub items: Vec<CompletionItem>,
+    pub location: ItemLocation
+}
+
+impl CompletionItem {
+    pub fn literal(label: &str) -> Self {
+        Self { label: label.to_string(), kind: CompletionKind::Literal, item_name: None }
+    }
+
+    pub fn suggestion(label: &str, item_name: String) -> Self {
+        Self { label: label.to_string(), kind: CompletionKind::Suggestion, item_name: Some(item_name) }
+    }
+}
+
+// The word being typed at the cursor is replaced by the completion. The content that precedes it is parsed,
+// and the items expected at its end are the candidates.
+pub(crate) fn complete_at<T : Parsable + 'static>(content: String, cursor: usize, options: ParseOptions) -> Completion {
+    let cursor = cursor.min(content.len());
+    let word_start = match content.is_char_boundary(cursor) {
+        true => cursor - content[..cursor].chars().rev().take_while(|c| c.is_xid_continue()).map(char::len_utf8).sum::<usize>(),
+        false => cursor
+    };
+    let word = content[word_start..cursor].to_string();
+    let mut items = vec![];
+
+    if content.is_char_boundary(cursor) {
+        let mut reader = StringReader::new(content[..word_start].to_string(), options.clone());
+
+        reader.set_completion_index(Some(word_start));
+        reader.eat_spaces();
+        reader.parse::<T>();
+
+        if reader.get_lexical_error().is_none() {
+            // A string can be both expected by the grammar and suggested by an item (e.g. a string variant of an enum),
+            // the suggestion is kept since it knows the name of the item
+            for item in reader.take_completion_items() {
+                if !item.label.starts_with(&word) {
+                    continue;
+                }
+
+                match items.iter_mut().find(|other: &&mut CompletionItem| other.label == item.label) {
+                    Some(other) if other.kind == CompletionKind::Literal => *other = item,
+                    Some(_) => {},
+                    None => items.push(item)
+                }
+            }
+        }
+    }
+
+    let file = SharedFileInfo::new(FileInfo::new(content, options.file_path.unwrap_or_default(), options.package_root_path.unwrap_or_default()));
+
+    Completion {
+        items,
+        location: ItemLocation { file, start: word_start, end: cursor }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Parsable, StringReader, ItemLocation, CommentSyntax};
+
+// Comment block directly preceding an item, e.g. `/// ...` lines on top of a declaration
+#[derive(Debug, Default, Clone)]
+pub struct DocComment {
+    pub content: String,
+    pub location: ItemLocation
+}
+
+impl DocComment {
+    pub fn is_empty(&self) -> bool {
+        self.location.length() == 0
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.content.lines()
+    }
+}
+
+impl Parsable for DocComment {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let index = reader.get_index();
+        let doc_comment_start = reader.get_doc_comment_start();
+        let mut comments = reader.get_preceding_comments();
+        let mut first_comment = comments.len();
+        let mut end = index;
+
+        for (i, (_, location)) in comments.iter().enumerate().rev() {
+            let content = location.file.content.as_str();
+            let line_start = content[..location.start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let gap = &content[location.end..end];
+            let is_doc_comment = doc_comment_start.is_none_or(|doc_comment_start| location.as_str().starts_with(doc_comment_start));
+
+            if !is_doc_comment || gap.matches('\n').count() > 1 || !content[line_start..location.start].trim().is_empty() {
+                break;
+            }
+
+            first_comment = i;
+            end = location.start;
+        }
+
+        let comments = comments.split_off(first_comment);
+        let location = match (comments.first(), comments.last()) {
+            (Some((_, first)), Some((_, last))) => ItemLocation {
+                file: first.file.clone(),
+                start: first.start,
+                end: last.end,
+            },
+            _ => reader.get_item_location(index).get_start(),
+        };
+        let lines : Vec<String> = comments.iter().flat_map(|(syntax, location)| strip_comment(location.as_str(), *syntax, doc_comment_start)).collect();
+
+        Some(Self {
+            content: lines.join("\n"),
+            location,
+        })
+    }
+
+    fn get_item_name() -> String {
+        "doc comment".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+
+    fn clone_item(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+}
+
+fn strip_comment(comment: &str, syntax: CommentSyntax, doc_comment_start: Option<&str>) -> Vec<String> {
+    let body = match syntax {
+        CommentSyntax::Line(start) => match doc_comment_start {
+            Some(doc_comment_start) => comment.strip_prefix(doc_comment_start).unwrap_or(comment),
+            None => {
+                // Also strips repeated delimiters, e.g. `///` when comments start with `//`
+                let last_char = start.chars().last().unwrap_or_default();
+
+                comment.strip_prefix(start).unwrap_or(comment).trim_start_matches(last_char)
+            }
+        },
+        CommentSyntax::Block(start, end) | CommentSyntax::NestedBlock(start, end) => {
+            let body = comment.strip_prefix(doc_comment_start.unwrap_or(start)).unwrap_or(comment);
+
+            body.strip_suffix(end).unwrap_or(body)
+        },
+    };
+
+    body.lines().map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end().to_string()).collect()
+}
@@ -0,0 +1,120 @@
+use std::fmt::Write;
+use crate::{ParseError, parse_error::ParseErrorKind};
+
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+pub struct ErrorRenderer {
+    pub color: bool,
+    pub context_lines: usize,
+    pub tab_width: usize
+}
+
+impl Default for ErrorRenderer {
+    fn default() -> Self {
+        Self {
+            color: false,
+            context_lines: 0,
+            tab_width: 4
+        }
+    }
+}
+
+impl ErrorRenderer {
+    pub fn render(&self, error: &ParseError) -> String {
+        let mut result = String::new();
+        let content = error.file.content.as_str();
+        let index = error.index.min(content.len());
+        let (line, col) = error.file.get_line_col(index).unwrap_or((1, 1));
+        let lines : Vec<&str> = content.split('\n').map(|line| line.strip_suffix('\r').unwrap_or(line)).collect();
+        let first_line = line.saturating_sub(self.context_lines).max(1);
+        let last_line = (line + self.context_lines).min(lines.len());
+        let gutter_width = last_line.to_string().len();
+        let gutter = " ".repeat(gutter_width);
+        let line_start = content[..index].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let caret_offset = self.get_display_width(&content[line_start..index]);
+
+        writeln!(result, "{}: {}", self.paint(RED, "error"), self.paint(BOLD, &get_expected_message(error))).unwrap();
+
+        let position = match error.file.path.is_empty() {
+            true => format!("{}:{}", line, col),
+            false => format!("{}:{}:{}", error.file.path, line, col),
+        };
+
+        // The content of a file that could not be read is not available
+        if let ParseErrorKind::Io(_) = error.kind {
+            write!(result, "{}{} {}", gutter, self.paint(BLUE, "-->"), error.file.path).unwrap();
+            return result;
+        }
+
+        writeln!(result, "{}{} {}", gutter, self.paint(BLUE, "-->"), position).unwrap();
+
+        writeln!(result, "{} {}", gutter, self.paint(BLUE, "|")).unwrap();
+
+        for line_number in first_line..=last_line {
+            let line_content = self.expand_tabs(lines[line_number - 1]);
+            let line_label = format!("{:>width$} |", line_number, width = gutter_width);
+
+            writeln!(result, "{} {}", self.paint(BLUE, &line_label), line_content).unwrap();
+
+            if line_number == line {
+                writeln!(result, "{} {} {}{}", gutter, self.paint(BLUE, "|"), " ".repeat(caret_offset), self.paint(RED, "^")).unwrap();
+            }
+        }
+
+        result.truncate(result.trim_end().len());
+        result
+    }
+
+    fn paint(&self, color: &str, text: &str) -> String {
+        match self.color {
+            true => format!("{}{}{}", color, text, RESET),
+            false => text.to_string(),
+        }
+    }
+
+    fn expand_tabs(&self, line: &str) -> String {
+        let mut result = String::new();
+
+        for c in line.chars() {
+            match c {
+                '\t' => {
+                    let width = self.tab_width - (result.chars().count() % self.tab_width.max(1));
+                    result.push_str(&" ".repeat(width));
+                },
+                _ => result.push(c)
+            }
+        }
+
+        result
+    }
+
+    fn get_display_width(&self, string: &str) -> usize {
+        self.expand_tabs(string).chars().count()
+    }
+}
+
+fn get_expected_message(error: &ParseError) -> String {
+    match &error.kind {
+        ParseErrorKind::UnterminatedComment => return "unterminated block comment".to_string(),
+        ParseErrorKind::InconsistentIndentation => return "inconsistent indentation".to_string(),
+        ParseErrorKind::InvalidRegex(pattern) => return format!("invalid regex /{}/", pattern),
+        ParseErrorKind::Io(kind) => return format!("cannot read file: {}", kind),
+        ParseErrorKind::InvalidUtf8 => return "invalid UTF-8".to_string(),
+        ParseErrorKind::IncludeCycle(path) => return format!("include cycle: {} is already being parsed", path),
+        ParseErrorKind::UnexpectedInput => {}
+    }
+
+    let mut expected = error.expected.clone();
+
+    expected.sort();
+    expected.dedup();
+
+    match expected.len() {
+        0 => "unexpected input".to_string(),
+        1 => format!("expected {}", expected[0]),
+        _ => format!("expected one of {}", expected.join(", ")),
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{Parsable, StringReader, ItemLocation};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right
+}
+
+pub trait ExpressionOperator : Parsable {
+    fn get_binary_precedence(&self) -> Option<(u32, Associativity)> {
+        None
+    }
+
+    fn get_prefix_precedence(&self) -> Option<u32> {
+        None
+    }
+
+    fn get_postfix_precedence(&self) -> Option<u32> {
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Expression<T, O> {
+    Operand(T),
+    Prefix {
+        operator: O,
+        operand: Box<Expression<T, O>>,
+        location: ItemLocation
+    },
+    Postfix {
+        operand: Box<Expression<T, O>>,
+        operator: O,
+        location: ItemLocation
+    },
+    Binary {
+        left: Box<Expression<T, O>>,
+        operator: O,
+        right: Box<Expression<T, O>>,
+        location: ItemLocation
+    }
+}
+
+impl<T : Parsable + 'static, O : ExpressionOperator + 'static> Expression<T, O> {
+    // Precedence climbing (Pratt parsing): `min_binding_power` is the binding power below which operators are left to the caller
+    fn parse_with_binding_power(reader: &mut StringReader, min_binding_power: u64) -> Option<Self> {
+        let start = reader.get_index();
+        let mut left = match Self::parse_prefix(reader) {
+            Some(prefix) => prefix,
+            None => match reader.parse::<T>() {
+                Some(operand) => {
+                    reader.eat_spaces();
+                    Self::Operand(operand)
+                },
+                None => {
+                    reader.set_expected_item::<T>();
+                    return None;
+                }
+            }
+        };
+
+        loop {
+            let operator_start = reader.get_index();
+            let operator = match reader.parse::<O>() {
+                Some(operator) => operator,
+                None => break
+            };
+
+            reader.eat_spaces();
+
+            if let Some(precedence) = operator.get_postfix_precedence() {
+                if get_binding_power(precedence, 0) < min_binding_power {
+                    reader.set_index(operator_start);
+                    break;
+                }
+
+                left = Self::Postfix {
+                    operand: Box::new(left),
+                    operator,
+                    location: reader.get_item_location(start),
+                };
+            } else if let Some((precedence, associativity)) = operator.get_binary_precedence() {
+                let (left_binding_power, right_binding_power) = match associativity {
+                    Associativity::Left => (get_binding_power(precedence, 0), get_binding_power(precedence, 1)),
+                    Associativity::Right => (get_binding_power(precedence, 1), get_binding_power(precedence, 0)),
+                };
+
+                if left_binding_power < min_binding_power {
+                    reader.set_index(operator_start);
+                    break;
+                }
+
+                match Self::parse_with_binding_power(reader, right_binding_power) {
+                    Some(right) => {
+                        left = Self::Binary {
+                            left: Box::new(left),
+                            operator,
+                            right: Box::new(right),
+                            location: reader.get_item_location(start),
+                        };
+                    },
+                    None => {
+                        reader.set_index(operator_start);
+                        break;
+                    }
+                }
+            } else {
+                reader.set_index(operator_start);
+                break;
+            }
+        }
+
+        Some(left)
+    }
+
+    // If the operand of a prefix operator does not match, the input may still be an operand (e.g. a negative literal)
+    fn parse_prefix(reader: &mut StringReader) -> Option<Self> {
+        let start = reader.get_index();
+        let operator = Self::parse_prefix_operator(reader)?;
+        let binding_power = get_binding_power(operator.get_prefix_precedence().unwrap(), 0);
+
+        match Self::parse_with_binding_power(reader, binding_power) {
+            Some(operand) => Some(Self::Prefix {
+                operator,
+                operand: Box::new(operand),
+                location: reader.get_item_location(start),
+            }),
+            None => {
+                reader.set_index(start);
+                None
+            }
+        }
+    }
+
+    fn parse_prefix_operator(reader: &mut StringReader) -> Option<O> {
+        let start = reader.get_index();
+
+        match reader.parse::<O>() {
+            Some(operator) if operator.get_prefix_precedence().is_some() => {
+                reader.eat_spaces();
+                Some(operator)
+            },
+            Some(_) => {
+                reader.set_index(start);
+                None
+            },
+            None => None
+        }
+    }
+}
+
+// Each precedence level has two binding powers, so that associativity can be expressed. They are computed
+// on 64 bits so that they remain distinct for all precedences.
+fn get_binding_power(precedence: u32, offset: u64) -> u64 {
+    precedence as u64 * 2 + offset
+}
+
+impl<T : Parsable + 'static, O : ExpressionOperator + 'static> Parsable for Expression<T, O> {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        Self::parse_with_binding_power(reader, 0)
+    }
+
+    fn get_item_name() -> String {
+        T::get_item_name()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        match self {
+            Self::Operand(operand) => operand.location(),
+            Self::Prefix { location, .. } => location,
+            Self::Postfix { location, .. } => location,
+            Self::Binary { location, .. } => location,
+        }
+    }
+
+    fn clone_item(&self) -> Option<Self> {
+        match self {
+            Self::Operand(operand) => operand.clone_item().map(Self::Operand),
+            Self::Prefix { operator, operand, location } => Some(Self::Prefix {
+                operator: operator.clone_item()?,
+                operand: operand.clone_item()?,
+                location: location.clone()
+            }),
+            Self::Postfix { operand, operator, location } => Some(Self::Postfix {
+                operand: operand.clone_item()?,
+                operator: operator.clone_item()?,
+                location: location.clone()
+            }),
+            Self::Binary { left, operator, right, location } => Some(Self::Binary {
+                left: left.clone_item()?,
+                operator: operator.clone_item()?,
+                right: right.clone_item()?,
+                location: location.clone()
+            }),
+        }
+    }
+}
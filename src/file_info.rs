@@ -1,8 +1,15 @@
 use std::hash::Hash;
-use crate::line_col_lookup::LineColLookup;
+use crate::{line_col_lookup::{LineColLookup, ColumnUnit}, position::Position, source_map::FileId};
+
+// With the `sync` feature, files are shared through `Arc` so that parsed items can be sent to other threads
+#[cfg(not(feature = "sync"))]
+pub type SharedFileInfo = std::rc::Rc<FileInfo>;
+#[cfg(feature = "sync")]
+pub type SharedFileInfo = std::sync::Arc<FileInfo>;
 
 #[derive(Debug, Default)]
 pub struct FileInfo {
+    pub id: Option<FileId>,
     pub content: String,
     pub path: String,
     pub package_root_path: String,
@@ -13,23 +20,60 @@ impl FileInfo {
     pub fn new(content: String, path: String, package_root_path: String) -> Self {
         let line_col_lookup = LineColLookup::new(&content);
 
-        Self { content, path, package_root_path, line_col_lookup }
+        Self { id: None, content, path, package_root_path, line_col_lookup }
+    }
+
+    pub fn with_id(mut self, id: FileId) -> Self {
+        self.id = Some(id);
+        self
     }
 
     pub fn get_line_col(&self, index: usize) -> Option<(usize, usize)> {
-        self.line_col_lookup.get(index)
+        self.line_col_lookup.get(&self.content, index, ColumnUnit::Char)
+    }
+
+    pub fn get_line_col_with_unit(&self, index: usize, unit: ColumnUnit) -> Option<(usize, usize)> {
+        self.line_col_lookup.get(&self.content, index, unit)
+    }
+
+    pub fn get_index(&self, line: usize, col: usize) -> Option<usize> {
+        self.line_col_lookup.get_index(&self.content, line, col, ColumnUnit::Char)
+    }
+
+    pub fn get_index_with_unit(&self, line: usize, col: usize, unit: ColumnUnit) -> Option<usize> {
+        self.line_col_lookup.get_index(&self.content, line, col, unit)
+    }
+
+    pub fn get_position(&self, index: usize, unit: ColumnUnit) -> Option<Position> {
+        self.get_line_col_with_unit(index, unit).map(|(line, col)| Position::new(line - 1, col - 1))
+    }
+
+    pub fn get_index_from_position(&self, position: Position, unit: ColumnUnit) -> Option<usize> {
+        self.get_index_with_unit(position.line + 1, position.character + 1, unit)
+    }
+
+    pub fn get_line_count(&self) -> usize {
+        self.line_col_lookup.get_line_count()
     }
 }
 
+// Files registered in a `SourceMap` are identified by their id, other files by their path
 impl PartialEq for FileInfo {
     fn eq(&self, other: &Self) -> bool {
-        self.path == other.path
+        match (self.id, other.id) {
+            (Some(id), Some(other_id)) => id == other_id,
+            (None, None) => self.path == other.path,
+            _ => false
+        }
     }
 }
 
 impl Hash for FileInfo {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.path.hash(state);
+        match self.id {
+            Some(id) => id.hash(state),
+            None => self.path.hash(state),
+        }
     }
 }
 
@@ -0,0 +1,684 @@
+use std::{collections::BTreeMap, rc::Rc, sync::Arc};
+use crate::{Parsable, Syntax, SyntaxWriter, SyntaxItem, ParseOptions, ParseError, ItemLocation, SharedFileInfo, Trivia, TriviaKind, TriviaList, DocComment, Newline, Indent, Dedent, Include, Expression, ExpressionOperator, Recover, Recoverable, to_source::are_joined};
+
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    pub width: usize,
+    pub indentation: &'static str
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            indentation: "    "
+        }
+    }
+}
+
+// Document laid out by the printer. The line breaks of a group are all written as spaces (or nothing) if its content fits
+// in the remaining width, otherwise they are all written as line breaks.
+#[derive(Debug, Clone, Default)]
+pub enum Doc {
+    #[default]
+    Nil,
+    // A space is inserted before the text if it would otherwise be read as one token with the previous one
+    Text(String),
+    // Space if the enclosing group fits on the line, line break otherwise
+    Line,
+    // Nothing if the enclosing group fits on the line, line break otherwise
+    SoftLine,
+    HardLine,
+    // Line break leaving an empty line
+    EmptyLine,
+    // Written at the end of the line, e.g. a line comment. Prevents the enclosing group from fitting on one line.
+    LineSuffix(String),
+    // Increase or decrease the indentation of the following lines, for `Indent` and `Dedent`
+    Indent,
+    Dedent,
+    Nest(Box<Doc>),
+    Group(Box<Doc>),
+    Concat(Vec<Doc>)
+}
+
+impl Doc {
+    pub fn text(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+
+    pub fn nest(doc: Doc) -> Self {
+        Self::Nest(Box::new(doc))
+    }
+
+    pub fn group(doc: Doc) -> Self {
+        Self::Group(Box::new(doc))
+    }
+
+    pub fn concat(docs: Vec<Doc>) -> Self {
+        Self::Concat(docs)
+    }
+
+    pub fn join(docs: Vec<Doc>, separator: Doc) -> Self {
+        let mut result = vec![];
+
+        for (i, doc) in docs.into_iter().enumerate() {
+            if i > 0 {
+                result.push(separator.clone());
+            }
+
+            result.push(doc);
+        }
+
+        Self::Concat(result)
+    }
+
+    pub fn render(&self, options: &FormatOptions) -> String {
+        let mut printer = Printer::new(options);
+        let mut stack = vec![(0, Mode::Break, self)];
+
+        while let Some((level, mode, doc)) = stack.pop() {
+            match doc {
+                Doc::Nil => {},
+                Doc::Text(text) => printer.text(text),
+                Doc::Line => match mode {
+                    Mode::Flat => printer.space(),
+                    Mode::Break => printer.line_break(level, 1),
+                },
+                Doc::SoftLine => if mode == Mode::Break {
+                    printer.line_break(level, 1);
+                },
+                Doc::HardLine => printer.line_break(level, 1),
+                Doc::EmptyLine => printer.line_break(level, 2),
+                Doc::LineSuffix(text) => printer.line_suffix(level, text),
+                Doc::Indent => printer.indentation_level += 1,
+                Doc::Dedent => printer.indentation_level = printer.indentation_level.saturating_sub(1),
+                Doc::Nest(doc) => stack.push((level + 1, mode, doc)),
+                Doc::Group(doc) => {
+                    let mode = match mode == Mode::Flat || printer.fits(doc, &stack) {
+                        true => Mode::Flat,
+                        false => Mode::Break,
+                    };
+
+                    stack.push((level, mode, doc));
+                },
+                Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (level, mode, doc))),
+            }
+        }
+
+        printer.finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break
+}
+
+// Like `SourceWriter`, line breaks are written lazily so that the indentation of the following line is known when it is written
+struct Printer<'a> {
+    options: &'a FormatOptions,
+    result: String,
+    column: usize,
+    indentation_level: usize,
+    pending_line_breaks: usize,
+    pending_level: usize,
+    line_suffix: String
+}
+
+impl<'a> Printer<'a> {
+    fn new(options: &'a FormatOptions) -> Self {
+        Self {
+            options,
+            result: String::new(),
+            column: 0,
+            indentation_level: 0,
+            pending_line_breaks: 0,
+            pending_level: 0,
+            line_suffix: String::new()
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        if self.pending_line_breaks > 0 {
+            let indentation = self.options.indentation.repeat(self.pending_level + self.indentation_level);
+
+            self.push(&"\n".repeat(self.pending_line_breaks));
+            self.push(&indentation);
+            self.pending_line_breaks = 0;
+        } else if let (Some(last), Some(first)) = (self.result.chars().last(), text.chars().next()) {
+            if are_joined(last, first) {
+                self.push(" ");
+            }
+        }
+
+        self.push(text);
+    }
+
+    fn space(&mut self) {
+        if self.pending_line_breaks == 0 && !self.result.is_empty() {
+            self.push(" ");
+        }
+    }
+
+    fn line_break(&mut self, level: usize, count: usize) {
+        if !self.line_suffix.is_empty() {
+            let line_suffix = std::mem::take(&mut self.line_suffix);
+
+            self.push(&line_suffix);
+        }
+
+        if !self.result.is_empty() {
+            self.result.truncate(self.result.trim_end_matches(' ').len());
+            self.pending_line_breaks = self.pending_line_breaks.max(count);
+            self.pending_level = level;
+        }
+    }
+
+    fn line_suffix(&mut self, level: usize, text: &str) {
+        // The line has already ended: the suffix is written at the end of it
+        if self.pending_line_breaks > 0 {
+            self.push(text);
+            return;
+        }
+
+        if !self.line_suffix.is_empty() {
+            self.line_break(level, 1);
+        }
+
+        self.line_suffix.push_str(text);
+    }
+
+    fn push(&mut self, text: &str) {
+        self.result.push_str(text);
+        self.column = match text.rfind('\n') {
+            Some(i) => text[i + 1..].chars().count(),
+            None => self.column + text.chars().count(),
+        };
+    }
+
+    // Whether `doc` fits on the line in flat mode, along with what follows it until the next line break
+    fn fits(&self, doc: &Doc, rest: &[(usize, Mode, &Doc)]) -> bool {
+        let (column, mut last) = match self.pending_line_breaks > 0 {
+            true => (self.options.indentation.chars().count() * (self.pending_level + self.indentation_level), None),
+            false => (self.column, self.result.chars().last()),
+        };
+        let mut remaining = self.options.width as isize - column as isize;
+        let mut stack = vec![(Mode::Flat, doc)];
+        let mut rest = rest.iter().rev();
+
+        loop {
+            let (mode, doc) = match stack.pop() {
+                Some(command) => command,
+                None => match rest.next() {
+                    Some((_, mode, doc)) => (*mode, *doc),
+                    None => return true,
+                },
+            };
+
+            match doc {
+                Doc::Nil | Doc::Indent | Doc::Dedent => {},
+                Doc::Text(text) => {
+                    let line = text.split('\n').next().unwrap_or_default();
+
+                    if let (Some(last), Some(first)) = (last, text.chars().next()) {
+                        remaining -= are_joined(last, first) as isize;
+                    }
+
+                    remaining -= line.chars().count() as isize;
+
+                    if line.len() < text.len() {
+                        return remaining >= 0;
+                    }
+
+                    last = text.chars().last().or(last);
+                },
+                Doc::Line => match mode {
+                    Mode::Flat => {
+                        remaining -= 1;
+                        last = Some(' ');
+                    },
+                    Mode::Break => return true,
+                },
+                Doc::SoftLine => if mode == Mode::Break {
+                    return true;
+                },
+                Doc::HardLine | Doc::EmptyLine => return mode == Mode::Break,
+                Doc::LineSuffix(_) => if mode == Mode::Flat {
+                    return false;
+                },
+                Doc::Nest(doc) | Doc::Group(doc) => stack.push((mode, doc)),
+                Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (mode, doc))),
+            }
+
+            if remaining < 0 {
+                return false;
+            }
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.line_break(0, 1);
+
+        if !self.result.is_empty() {
+            self.result.push('\n');
+        }
+
+        self.result
+    }
+}
+
+// Builds the document of a tree, and inserts the comments collected by the parser before, after or at the end
+// of the items whose locations surround them. Comments are written in the order they appear in the source.
+pub struct Formatter {
+    options: FormatOptions,
+    comments: BTreeMap<usize, Trivia>,
+    file: Option<SharedFileInfo>,
+    index: usize
+}
+
+impl Formatter {
+    pub fn new(options: FormatOptions, trivia: TriviaList) -> Self {
+        Self {
+            options,
+            comments: trivia.comments().map(|comment| (comment.location.start, comment.clone())).collect(),
+            file: None,
+            index: 0
+        }
+    }
+
+    pub fn get_options(&self) -> &FormatOptions {
+        &self.options
+    }
+
+    pub fn format<T : Format>(&mut self, item: &T) -> Doc {
+        let location = match item.get_format_location() {
+            Some(location) if self.is_in_file(location) => location.clone(),
+            _ => return item.format(self),
+        };
+        let mut docs = self.take_leading_comments(location.start);
+
+        docs.push(item.format(self));
+        docs.extend(self.take_comments_until(location.end));
+        docs.extend(self.take_trailing_comments(&location));
+        self.index = self.index.max(location.end);
+
+        Doc::concat(docs)
+    }
+
+    // Document of the literals and fields of an item, e.g. to wrap it in a custom layout
+    pub fn format_syntax<T : for<'a> Syntax<DocWriter<'a>>>(&mut self, item: &T) -> Doc {
+        let mut writer = DocWriter { formatter: self, docs: vec![] };
+
+        item.write_syntax(&mut writer);
+        Doc::concat(writer.docs)
+    }
+
+    // Items of a list between brackets: on one line if it fits, otherwise one item per line
+    pub fn format_separated<T : Format>(&mut self, items: &[T], separator: &str) -> Doc {
+        if items.is_empty() {
+            return Doc::Nil;
+        }
+
+        let docs = items.iter().map(|item| self.format(item)).collect();
+
+        Doc::group(Doc::concat(vec![
+            Doc::nest(Doc::concat(vec![Doc::SoftLine, Doc::join(docs, Doc::concat(vec![Doc::text(separator), Doc::Line]))])),
+            Doc::SoftLine
+        ]))
+    }
+
+    // Comments that have not been written yet are added at the end
+    pub fn finish(mut self, doc: Doc) -> String {
+        let mut docs = vec![doc];
+
+        while let Some((_, comment)) = self.comments.pop_first() {
+            docs.push(match is_on_own_line(&comment) {
+                true => self.get_own_line_comment_doc(&comment),
+                false => get_trailing_comment_doc(&comment),
+            });
+            self.index = comment.location.end;
+        }
+
+        Doc::concat(docs).render(&self.options)
+    }
+
+    fn is_in_file(&mut self, location: &ItemLocation) -> bool {
+        match &self.file {
+            Some(file) => *location.file == **file,
+            None => {
+                self.file = Some(location.file.clone());
+                true
+            }
+        }
+    }
+
+    fn get_content(&self) -> &str {
+        self.file.as_ref().map(|file| file.content.as_str()).unwrap_or_default()
+    }
+
+    // Empty lines of the source between the last written item and `index` are preserved
+    fn get_line_break_before(&self, index: usize, default: Doc) -> Doc {
+        let content = self.get_content();
+        let has_empty_line = index > self.index && index <= content.len() && {
+            let lines : Vec<&str> = content[self.index..index].split('\n').collect();
+
+            lines.len() > 2 && lines[1..lines.len() - 1].iter().any(|line| line.trim().is_empty())
+        };
+
+        match has_empty_line {
+            true => Doc::EmptyLine,
+            false => default,
+        }
+    }
+
+    fn get_own_line_comment_doc(&self, comment: &Trivia) -> Doc {
+        Doc::concat(vec![self.get_line_break_before(comment.location.start, Doc::HardLine), Doc::text(comment.as_str())])
+    }
+
+    fn take_leading_comments(&mut self, start: usize) -> Vec<Doc> {
+        let mut docs = vec![];
+
+        while let Some(entry) = self.comments.first_entry().filter(|entry| *entry.key() < start) {
+            let comment = entry.remove();
+            let next = self.comments.first_key_value().map(|(index, _)| *index).filter(|index| *index < start).unwrap_or(start);
+            let is_followed_by_line_break = comment.kind == TriviaKind::LineComment || comment.location.file.content[comment.location.end..next].contains('\n');
+
+            docs.push(match (is_on_own_line(&comment), comment.kind) {
+                (true, _) => self.get_own_line_comment_doc(&comment),
+                // Kept at the end of the line it follows, e.g. after a separator
+                (false, TriviaKind::LineComment) => get_trailing_comment_doc(&comment),
+                (false, _) => Doc::text(comment.as_str()),
+            });
+            docs.push(match is_followed_by_line_break {
+                true => Doc::HardLine,
+                false => Doc::text(" "),
+            });
+
+            self.index = comment.location.end;
+        }
+
+        docs.push(self.get_line_break_before(start, Doc::Nil));
+        self.index = self.index.max(start);
+
+        docs
+    }
+
+    // Comments inside an item that were not attached to any of its children are written at its end
+    fn take_comments_until(&mut self, end: usize) -> Vec<Doc> {
+        let mut docs = vec![];
+
+        while let Some(entry) = self.comments.first_entry().filter(|entry| entry.get().location.end <= end) {
+            let comment = entry.remove();
+
+            docs.push(match is_on_own_line(&comment) {
+                true => Doc::concat(vec![self.get_own_line_comment_doc(&comment), Doc::HardLine]),
+                false => get_trailing_comment_doc(&comment),
+            });
+            self.index = comment.location.end;
+        }
+
+        docs
+    }
+
+    // Comments following the item on the same line, if the item does not end with a line break
+    fn take_trailing_comments(&mut self, location: &ItemLocation) -> Vec<Doc> {
+        let content = location.file.content.as_str();
+        let text = location.as_str();
+        let mut docs = vec![];
+        let mut index = location.end;
+
+        if text[text.trim_end().len()..].contains('\n') {
+            return docs;
+        }
+
+        while let Some(entry) = self.comments.first_entry() {
+            if !content.get(index..*entry.key()).is_some_and(|gap| gap.chars().all(|c| c == ' ' || c == '\t')) {
+                break;
+            }
+
+            let comment = entry.remove();
+
+            docs.push(get_trailing_comment_doc(&comment));
+            index = comment.location.end;
+            self.index = index;
+        }
+
+        docs
+    }
+}
+
+// Builds the documents of the literals and fields of an item, see `Formatter::format_syntax()`
+pub struct DocWriter<'a> {
+    formatter: &'a mut Formatter,
+    docs: Vec<Doc>
+}
+
+impl SyntaxWriter for DocWriter<'_> {
+    fn literal(&mut self, literal: &'static str) {
+        self.docs.push(Doc::text(literal));
+    }
+}
+
+impl<T : Format> SyntaxItem<DocWriter<'_>> for T {
+    fn write_item(&self, writer: &mut DocWriter) {
+        let doc = writer.formatter.format(self);
+
+        writer.docs.push(doc);
+    }
+
+    fn write_separated(items: &[Self], separator: &'static str, writer: &mut DocWriter) {
+        let doc = writer.formatter.format_separated(items, separator);
+
+        writer.docs.push(doc);
+    }
+
+    fn is_empty_item(&self) -> bool {
+        self.is_empty_format()
+    }
+}
+
+fn is_on_own_line(comment: &Trivia) -> bool {
+    let content = comment.location.file.content.as_str();
+    let line_start = content[..comment.location.start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    content[line_start..comment.location.start].trim().is_empty()
+}
+
+fn get_trailing_comment_doc(comment: &Trivia) -> Doc {
+    match comment.kind {
+        TriviaKind::LineComment => Doc::LineSuffix(format!(" {}", comment.as_str())),
+        _ => Doc::Text(format!(" {}", comment.as_str())),
+    }
+}
+
+// Items are formatted through `Formatter::format()`, which inserts the comments around them
+pub trait Format {
+    fn format(&self, formatter: &mut Formatter) -> Doc;
+
+    // Location used to attach comments to the item. Containers (`Vec`, `Option`, `Box`...) and enums have none.
+    fn get_format_location(&self) -> Option<&ItemLocation> {
+        None
+    }
+
+    fn is_empty_format(&self) -> bool {
+        false
+    }
+
+    fn format_source(content: String, parse_options: ParseOptions, options: FormatOptions) -> Result<String, ParseError> where Self : Parsable + 'static {
+        let (item, trivia) = Self::parse_with_trivia(content, ParseOptions { collect_comments: true, ..parse_options })?;
+        let mut formatter = Formatter::new(options, trivia);
+        let doc = formatter.format(&item);
+
+        Ok(formatter.finish(doc))
+    }
+}
+
+impl Format for String {
+    fn format(&self, _formatter: &mut Formatter) -> Doc {
+        Doc::text(self)
+    }
+
+    fn is_empty_format(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl Format for () {
+    fn format(&self, _formatter: &mut Formatter) -> Doc {
+        Doc::Nil
+    }
+
+    fn is_empty_format(&self) -> bool {
+        true
+    }
+}
+
+impl<T : Format> Format for Box<T> {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        formatter.format(self.as_ref())
+    }
+
+    fn is_empty_format(&self) -> bool {
+        self.as_ref().is_empty_format()
+    }
+}
+
+impl<T : Format> Format for Rc<T> {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        formatter.format(self.as_ref())
+    }
+
+    fn is_empty_format(&self) -> bool {
+        self.as_ref().is_empty_format()
+    }
+}
+
+impl<T : Format> Format for Arc<T> {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        formatter.format(self.as_ref())
+    }
+
+    fn is_empty_format(&self) -> bool {
+        self.as_ref().is_empty_format()
+    }
+}
+
+impl<T : Format> Format for Option<T> {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        match self {
+            Some(item) => formatter.format(item),
+            None => Doc::Nil,
+        }
+    }
+
+    fn is_empty_format(&self) -> bool {
+        self.is_none()
+    }
+}
+
+impl<T : Format> Format for Vec<T> {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        Doc::concat(self.iter().map(|item| formatter.format(item)).collect())
+    }
+
+    fn is_empty_format(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T : Format, U : Format> Format for (T, U) {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        Doc::concat(vec![formatter.format(&self.0), formatter.format(&self.1)])
+    }
+}
+
+// Doc comments are collected along with the other comments, and written as they are in the source
+impl Format for DocComment {
+    fn format(&self, _formatter: &mut Formatter) -> Doc {
+        Doc::Nil
+    }
+
+    fn is_empty_format(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+// Comments after the line break (including the following lines) are left to the next item
+impl Format for Newline {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        let mut docs = formatter.take_leading_comments(self.location.start);
+
+        docs.push(Doc::HardLine);
+        Doc::concat(docs)
+    }
+}
+
+impl Format for Indent {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        let mut docs = formatter.take_leading_comments(self.location.start);
+
+        docs.extend([Doc::Indent, Doc::HardLine]);
+        Doc::concat(docs)
+    }
+}
+
+impl Format for Dedent {
+    fn format(&self, _formatter: &mut Formatter) -> Doc {
+        Doc::concat(vec![Doc::Dedent, Doc::HardLine])
+    }
+}
+
+// The included file is not formatted, only the path is written
+impl<T : Parsable> Format for Include<T> {
+    fn format(&self, _formatter: &mut Formatter) -> Doc {
+        Doc::Text(format!("\"{}\"", self.path))
+    }
+
+    fn get_format_location(&self) -> Option<&ItemLocation> {
+        Some(&self.location)
+    }
+}
+
+impl<T : Recover + Format> Format for Recoverable<T> {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        match self {
+            Self::Item(item) => formatter.format(item),
+            Self::Error(location) => Doc::text(location.as_str().trim()),
+        }
+    }
+
+    fn get_format_location(&self) -> Option<&ItemLocation> {
+        match self {
+            Self::Item(_) => None,
+            Self::Error(location) => Some(location),
+        }
+    }
+}
+
+// The right operand of a binary expression is moved to the next line if the expression does not fit
+impl<T : Parsable + Format + 'static, O : ExpressionOperator + Format + 'static> Format for Expression<T, O> {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        match self {
+            Self::Operand(operand) => formatter.format(operand),
+            Self::Prefix { operator, operand, .. } => Doc::concat(vec![formatter.format(operator), formatter.format(operand.as_ref())]),
+            Self::Postfix { operand, operator, .. } => Doc::concat(vec![formatter.format(operand.as_ref()), formatter.format(operator)]),
+            Self::Binary { left, operator, right, .. } => Doc::group(Doc::concat(vec![
+                formatter.format(left.as_ref()),
+                formatter.format(operator),
+                Doc::nest(Doc::concat(vec![Doc::SoftLine, formatter.format(right.as_ref())]))
+            ])),
+        }
+    }
+
+    fn get_format_location(&self) -> Option<&ItemLocation> {
+        match self {
+            Self::Operand(_) => None,
+            _ => Some(self.location()),
+        }
+    }
+}
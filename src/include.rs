@@ -0,0 +1,43 @@
+use crate::{Parsable, StringReader, ItemLocation};
+
+// Path between double quotes, followed by the content of the file it refers to
+#[derive(Debug, Clone)]
+pub struct Include<T> {
+    pub path: String,
+    pub item: T,
+    pub location: ItemLocation
+}
+
+impl<T : Parsable + 'static> Parsable for Include<T> {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let start = reader.get_index();
+        let literal = reader.read_regex(r#""[^"\n]*""#)?;
+        let path = literal[1..literal.len() - 1].to_string();
+        let location = reader.get_item_location(start);
+
+        match reader.parse_included_file::<T>(&path, start) {
+            Ok(item) => Some(Self { path, item, location }),
+            Err(error) => {
+                reader.set_nested_error(error);
+                reader.set_index(start);
+                None
+            }
+        }
+    }
+
+    fn get_item_name() -> String {
+        "file path".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+
+    fn clone_item(&self) -> Option<Self> {
+        Some(Self {
+            path: self.path.clone(),
+            item: self.item.clone_item()?,
+            location: self.location.clone()
+        })
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{Parsable, StringReader, ItemLocation, ParseErrorKind};
+
+// Stack of indentation levels, each level being the exact whitespace string that starts its lines
+#[derive(Clone)]
+pub struct IndentationStack {
+    levels: Vec<String>,
+    changes: Vec<IndentationChange>
+}
+
+#[derive(Clone)]
+enum IndentationChange {
+    Push { index: usize },
+    Pop { index: usize, frame_id: usize, level: String }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentationComparison {
+    Same,
+    Deeper,
+    Shallower,
+    Inconsistent
+}
+
+impl IndentationStack {
+    pub fn new() -> Self {
+        Self {
+            levels: vec![String::new()],
+            changes: vec![]
+        }
+    }
+
+    pub fn compare(&self, indentation: &str) -> IndentationComparison {
+        let current = self.levels.last().unwrap();
+
+        if indentation == current {
+            IndentationComparison::Same
+        } else if indentation.starts_with(current.as_str()) {
+            IndentationComparison::Deeper
+        } else if current.starts_with(indentation) {
+            IndentationComparison::Shallower
+        } else {
+            IndentationComparison::Inconsistent
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn push(&mut self, indentation: String, index: usize) {
+        self.levels.push(indentation);
+        self.changes.push(IndentationChange::Push { index });
+    }
+
+    pub fn pop(&mut self, index: usize, frame_id: usize) {
+        if self.levels.len() > 1 {
+            let level = self.levels.pop().unwrap();
+
+            self.changes.push(IndentationChange::Pop { index, frame_id, level });
+        }
+    }
+
+    // Pushes are made by tokens that consume input, so they are undone when going back to their start.
+    // Pops are zero-width: going back to their index only undoes the ones made by the item that backtracks
+    // (the frame `frame_id` or the frames it started), not the ones of the siblings that matched before it.
+    pub fn backtrack(&mut self, index: usize, frame_id: usize) {
+        loop {
+            match self.changes.last() {
+                Some(IndentationChange::Push { index: push_index }) if *push_index >= index => {
+                    self.levels.pop();
+                },
+                Some(IndentationChange::Pop { index: pop_index, frame_id: pop_frame_id, level }) if *pop_index >= index && (*pop_index > index || *pop_frame_id >= frame_id) => {
+                    self.levels.push(level.clone());
+                },
+                _ => break
+            }
+
+            self.changes.pop();
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Newline {
+    pub location: ItemLocation
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Indent {
+    pub location: ItemLocation
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Dedent {
+    pub location: ItemLocation
+}
+
+impl Parsable for Newline {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let start = reader.get_index();
+        let indentation = reader.read_line_break()?;
+
+        // The indentation of a shallower line is left to `Dedent`
+        if reader.is_indentation_sensitive() {
+            match reader.compare_indentation(&indentation) {
+                IndentationComparison::Same => reader.skip_indentation(),
+                IndentationComparison::Shallower => {},
+                IndentationComparison::Deeper => {
+                    reader.set_index(start);
+                    return None;
+                },
+                IndentationComparison::Inconsistent => {
+                    reader.set_lexical_error(ParseErrorKind::InconsistentIndentation, reader.get_index());
+                    reader.set_index(start);
+                    return None;
+                }
+            }
+        } else {
+            reader.skip_indentation();
+        }
+
+        Some(Self { location: reader.get_item_location(start) })
+    }
+
+    fn get_item_name() -> String {
+        "newline".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+
+    fn clone_item(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+}
+
+impl Parsable for Indent {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let start = reader.get_index();
+        let indentation = reader.read_line_break()?;
+
+        match reader.compare_indentation(&indentation) {
+            IndentationComparison::Deeper => {
+                reader.skip_indentation();
+                reader.push_indentation(indentation, start);
+
+                Some(Self { location: reader.get_item_location(start) })
+            },
+            comparison => {
+                if comparison == IndentationComparison::Inconsistent {
+                    reader.set_lexical_error(ParseErrorKind::InconsistentIndentation, reader.get_index());
+                }
+
+                reader.set_index(start);
+                None
+            }
+        }
+    }
+
+    fn get_item_name() -> String {
+        "indent".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+
+    fn clone_item(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+}
+
+impl Parsable for Dedent {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let index = reader.get_index();
+        let indentation = reader.get_line_indentation()?;
+
+        match reader.compare_indentation(&indentation) {
+            IndentationComparison::Shallower => {
+                reader.pop_indentation(index);
+
+                // Consecutive dedents are zero-width, the last one consumes the indentation
+                match reader.compare_indentation(&indentation) {
+                    IndentationComparison::Same => reader.skip_indentation(),
+                    IndentationComparison::Shallower => {},
+                    _ => reader.set_lexical_error(ParseErrorKind::InconsistentIndentation, index)
+                }
+
+                Some(Self { location: reader.get_item_location(index).get_start() })
+            },
+            IndentationComparison::Inconsistent => {
+                reader.set_lexical_error(ParseErrorKind::InconsistentIndentation, index);
+                None
+            },
+            _ => None
+        }
+    }
+
+    fn get_item_name() -> String {
+        "dedent".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+
+    fn clone_item(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+}
@@ -1,9 +1,9 @@
-use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}, rc::Rc, fmt::Debug, path::Path};
-use crate::file_info::FileInfo;
+use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}, fmt::Debug, path::Path};
+use crate::{file_info::SharedFileInfo, line_col_lookup::ColumnUnit, position::{Position, Range}};
 
 #[derive(Clone, Default)]
 pub struct ItemLocation {
-    pub file: Rc<FileInfo>,
+    pub file: SharedFileInfo,
     pub start: usize,
     pub end: usize,
 }
@@ -29,6 +29,32 @@ impl ItemLocation {
         self.file.get_line_col(self.start).unwrap()
     }
 
+    pub fn get_start_position(&self, unit: ColumnUnit) -> Position {
+        self.file.get_position(self.start, unit).unwrap()
+    }
+
+    pub fn get_end_position(&self, unit: ColumnUnit) -> Position {
+        self.file.get_position(self.end, unit).unwrap()
+    }
+
+    pub fn to_range(&self, unit: ColumnUnit) -> Range {
+        Range::new(self.get_start_position(unit), self.get_end_position(unit))
+    }
+
+    pub fn from_range(file: &SharedFileInfo, range: Range, unit: ColumnUnit) -> Option<Self> {
+        let start = file.get_index_from_position(range.start, unit)?;
+        let end = file.get_index_from_position(range.end, unit)?;
+
+        match start <= end {
+            true => Some(Self { file: file.clone(), start, end }),
+            false => None
+        }
+    }
+
+    pub fn get_start(&self) -> Self {
+        self.clone().set_bounds(self.start)
+    }
+
     pub fn get_end(&self) -> Self {
         self.clone().set_bounds(self.end)
     }
@@ -94,7 +120,7 @@ impl ItemLocation {
 
 impl Hash for ItemLocation {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.file.path.hash(state);
+        self.file.hash(state);
         self.start.hash(state);
         self.end.hash(state);
     }
@@ -104,7 +130,10 @@ impl PartialEq for ItemLocation {
     fn eq(&self, other: &Self) -> bool {
         self.start == other.start &&
         self.end == other.end &&
-        Rc::ptr_eq(&self.file, &other.file)
+        match (self.file.id, other.file.id) {
+            (Some(id), Some(other_id)) => id == other_id,
+            _ => SharedFileInfo::ptr_eq(&self.file, &other.file)
+        }
     }
 }
 
@@ -1,3 +1,5 @@
+#![allow(clippy::manual_map, clippy::manual_find, clippy::match_like_matches_macro)]
+
 mod utils;
 mod parsable;
 mod string_reader;
@@ -10,11 +12,51 @@ mod parse_options;
 mod token;
 mod end_of_file;
 mod marker_list;
+mod memo_table;
+mod expression;
+mod error_renderer;
+mod recoverable;
+mod position;
+mod trivia;
+mod doc_comment;
+mod indentation;
+mod source_map;
+mod include;
+mod completion;
+mod node;
+mod visit;
+mod syntax;
+mod to_source;
+mod format;
+#[cfg(feature = "sync")]
+mod parse_files;
 
 pub use parsable::Parsable;
 pub use string_reader::StringReader;
-pub use parse_options::ParseOptions;
-pub use parse_error::ParseError;
+pub use parse_options::{ParseOptions, CommentSyntax};
+pub use parse_error::{ParseError, ParseErrorKind};
+pub use error_renderer::ErrorRenderer;
+pub use recoverable::{Recover, Recoverable, RecoveryTokens};
 pub use item_location::ItemLocation;
 pub use parsable_macro::*;
-pub use file_info::FileInfo;
\ No newline at end of file
+pub use file_info::{FileInfo, SharedFileInfo};
+pub use source_map::{SourceMap, FileId};
+pub use line_col_lookup::ColumnUnit;
+pub use position::{Position, Range};
+pub use trivia::{Trivia, TriviaKind, TriviaList};
+pub use doc_comment::DocComment;
+pub use indentation::{Newline, Indent, Dedent};
+pub use include::Include;
+pub use node::Node;
+#[doc(hidden)]
+pub use node::{NodeField, NodeFieldImpl, NodeFieldFallback};
+pub use syntax::{Syntax, SyntaxWriter, SyntaxItem};
+pub use to_source::{ToSource, SourceWriter, SourceOptions, Spacing};
+pub use format::{Format, Formatter, FormatOptions, Doc, DocWriter};
+pub use visit::{Visit, VisitMut, Visitor, VisitorMut};
+#[doc(hidden)]
+pub use visit::{VisitField, VisitFieldMut, VisitFieldImpl, VisitFieldFallback, VisitFieldMutImpl, VisitFieldMutFallback};
+pub use completion::{Completion, CompletionItem, CompletionKind};
+#[cfg(feature = "sync")]
+pub use parse_files::parse_files;
+pub use expression::{Expression, ExpressionOperator, Associativity};
\ No newline at end of file
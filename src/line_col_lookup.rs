@@ -1,33 +1,92 @@
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColumnUnit {
+    Byte,
+    Char,
+    Utf16
+}
+
+// Built with the file rather than on first use, so that files (and the locations that refer to them) have no interior mutability
+#[derive(Debug)]
 pub struct LineColLookup {
-    pub lookup: Vec<(usize, usize)>
+    line_starts: Vec<usize>
+}
+
+impl ColumnUnit {
+    pub fn get_char_length(&self, c: char) -> usize {
+        match self {
+            ColumnUnit::Byte => c.len_utf8(),
+            ColumnUnit::Char => 1,
+            ColumnUnit::Utf16 => c.len_utf16(),
+        }
+    }
+}
+
+impl Default for LineColLookup {
+    fn default() -> Self {
+        Self::new("")
+    }
 }
 
 impl LineColLookup {
     pub fn new(string: &str) -> Self {
-        let mut lookup = Vec::with_capacity(string.len() + 1);
-        let mut line = 1;
-        let mut col = 1;
-        
-        for byte in string.as_bytes() {
-            let c = *byte as char;
-
-            lookup.push((line, col));
-
-            if c == '\n' {
-                line += 1;
-                col = 1;
-            } else {
-                col += 1;
+        let mut line_starts = vec![0];
+
+        for (i, byte) in string.bytes().enumerate() {
+            if byte == b'\n' {
+                line_starts.push(i + 1);
             }
         }
 
-        lookup.push((line, col));
+        Self { line_starts }
+    }
+
+    pub fn get(&self, string: &str, index: usize, unit: ColumnUnit) -> Option<(usize, usize)> {
+        if index > string.len() {
+            return None;
+        }
+
+        let mut index = index;
 
-        Self { lookup }
+        while !string.is_char_boundary(index) {
+            index -= 1;
+        }
+
+        let line_starts = &self.line_starts;
+        let line = line_starts.partition_point(|line_start| *line_start <= index);
+        let col = string[line_starts[line - 1]..index].chars().map(|c| unit.get_char_length(c)).sum::<usize>() + 1;
+
+        Some((line, col))
+    }
+
+    pub fn get_index(&self, string: &str, line: usize, col: usize, unit: ColumnUnit) -> Option<usize> {
+        let line_starts = &self.line_starts;
+        let line_start = *line_starts.get(line.checked_sub(1)?)?;
+        let line_end = line_starts.get(line).map(|next_line_start| next_line_start - 1).unwrap_or(string.len());
+        let mut remaining = col.checked_sub(1)?;
+        let mut index = line_start;
+
+        for c in string[line_start..line_end].chars() {
+            if remaining == 0 {
+                break;
+            }
+
+            let length = unit.get_char_length(c);
+
+            if length > remaining {
+                return None;
+            }
+
+            remaining -= length;
+            index += c.len_utf8();
+        }
+
+        match remaining {
+            0 => Some(index),
+            _ => None
+        }
     }
 
-    pub fn get(&self, index: usize) -> Option<(usize, usize)> {
-        self.lookup.get(index).cloned()
+    pub fn get_line_count(&self) -> usize {
+        self.line_starts.len()
     }
 }
\ No newline at end of file
@@ -1,8 +1,14 @@
+use std::rc::Rc;
+
 pub struct MarkerList {
     markers: Vec<Marker>,
-    counter: u64
+    counter: u64,
+    state: Option<MarkerState>
 }
 
+// Names and values of the declared markers, in the order they are declared
+pub type MarkerState = Rc<[(&'static str, bool)]>;
+
 struct Marker {
     name: &'static str,
     id: u64,
@@ -13,7 +19,8 @@ impl MarkerList {
     pub fn new() -> Self {
         Self {
             markers: vec![],
-            counter: 1
+            counter: 1,
+            state: None
         }
     }
 
@@ -24,6 +31,7 @@ impl MarkerList {
 
         self.counter += 1;
         self.markers.push(marker);
+        self.state = None;
 
         id
     }
@@ -32,6 +40,7 @@ impl MarkerList {
         let index = self.markers.iter().position(|marker| marker.id == id).unwrap();
 
         self.markers.remove(index);
+        self.state = None;
     }
 
     fn get_by_name(&self, name: &'static str) -> Option<&Marker> {
@@ -58,14 +67,28 @@ impl MarkerList {
         self.get_by_name(name).map(|marker| marker.value).unwrap_or(false)
     }
 
+    pub fn get_declared(&self, name: &'static str) -> Option<bool> {
+        self.get_by_name(name).map(|marker| marker.value)
+    }
+
     pub fn set(&mut self, name: &'static str, value: bool) -> bool {
         if let Some(marker) = self.get_by_name_mut(name) {
             let prev_value = marker.value;
             marker.value = value;
+
+            if prev_value != value {
+                self.state = None;
+            }
+
             prev_value
         } else {
             // panic!("marker {} does not exist", name);
             false
         }
     }
+
+    // The state is shared until a marker changes, so that it is not rebuilt for each item
+    pub fn get_state(&mut self) -> MarkerState {
+        self.state.get_or_insert_with(|| self.markers.iter().map(|marker| (marker.name, marker.value)).collect()).clone()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,114 @@
+use std::{any::{Any, TypeId}, collections::HashMap};
+use crate::{completion::CompletionItem, ParseError, trivia::{Trivia, TriviaList}, indentation::IndentationStack, string_reader::LexicalError, marker_list::MarkerState};
+
+// Memoization table used for packrat parsing
+pub struct MemoTable {
+    entries: HashMap<MemoKey, MemoEntry>
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct MemoKey {
+    type_id: TypeId,
+    index: usize,
+    state: ParseState
+}
+
+// The result of an item depends on the markers and on the indentation level
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ParseState {
+    pub markers: MarkerState,
+    pub indentation_depth: usize
+}
+
+pub struct MemoEntry {
+    pub value: Option<Box<dyn Any>>,
+    pub end: usize,
+    pub expected: ExpectedTrace,
+    pub errors: Vec<(usize, ParseError)>,
+    pub lexical_error: Option<ParseError>,
+    pub trivia: Vec<Trivia>
+}
+
+// Item being parsed. Parsing an item again at the same index means that it is left-recursive, which is resolved
+// following "Packrat Parsers Can Support Left Recursion" (Warth et al.): the first match is used as a seed,
+// which is grown as long as parsing the item again (with the seed as the result of the recursive call) consumes more input.
+// Frames are numbered in the order they are started, so the ones started by a frame have a greater id.
+pub struct ParseFrame {
+    pub id: usize,
+    pub rule: TypeId,
+    pub index: usize,
+    pub state: ParseState,
+    pub left_recursive: bool,
+    pub involved: bool,
+    pub seed: Option<(Box<dyn Any>, usize)>
+}
+
+// State restored when an iteration of a left recursion does not consume more input than the seed
+pub struct ReaderState {
+    pub errors: Vec<(usize, ParseError)>,
+    pub lexical_error: Option<LexicalError>,
+    pub indentation: Option<IndentationStack>,
+    pub trivia: TriviaList
+}
+
+// Only the expected items registered at the furthest index can survive in the reader,
+// so this is all that is needed to replay the error bookkeeping of a cached parse.
+#[derive(Default, Clone)]
+pub struct ExpectedTrace {
+    pub index: Option<usize>,
+    pub items: Vec<String>,
+    pub completion_items: Vec<CompletionItem>
+}
+
+impl MemoTable {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new()
+        }
+    }
+
+    pub fn get(&self, type_id: TypeId, index: usize, state: &ParseState) -> Option<&MemoEntry> {
+        self.entries.get(&MemoKey { type_id, index, state: state.clone() })
+    }
+
+    pub fn insert(&mut self, type_id: TypeId, index: usize, state: ParseState, entry: MemoEntry) {
+        self.entries.insert(MemoKey { type_id, index, state }, entry);
+    }
+}
+
+impl ParseFrame {
+    pub fn new(id: usize, rule: TypeId, index: usize, state: ParseState) -> Self {
+        Self {
+            id,
+            rule,
+            index,
+            state,
+            left_recursive: false,
+            involved: false,
+            seed: None
+        }
+    }
+}
+
+impl ExpectedTrace {
+    pub fn record(&mut self, index: usize, item: String) {
+        match self.index {
+            Some(current) if current == index && !self.items.contains(&item) => self.items.push(item),
+            Some(current) if current >= index => {},
+            _ => {
+                self.index = Some(index);
+                self.items = vec![item];
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: &ExpectedTrace) {
+        self.completion_items.extend(other.completion_items.iter().cloned());
+
+        if let Some(index) = other.index {
+            for item in &other.items {
+                self.record(index, item.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use std::{rc::Rc, sync::Arc};
+use crate::{Parsable, ItemLocation, DocComment, Newline, Indent, Dedent, Include, Expression, ExpressionOperator, Recover, Recoverable};
+
+// Generic view of a parsed tree. Containers (`Vec`, `Option`, `Box`...) are transparent: their content
+// is added directly to the children of the item that holds them.
+pub trait Node {
+    fn get_node_name(&self) -> String;
+
+    fn get_node_location(&self) -> Option<&ItemLocation>;
+
+    #[allow(unused_variables)]
+    fn collect_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+
+    }
+
+    fn collect_as_child<'a>(&'a self, children: &mut Vec<&'a dyn Node>) where Self : Sized {
+        children.push(self);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Node> {
+        let mut children = vec![];
+
+        self.collect_children(&mut children);
+
+        children
+    }
+
+    // Chain of nodes (outermost to innermost) whose location contains `index`
+    fn node_at(&self, index: usize) -> Vec<&dyn Node> where Self : Sized {
+        let mut chain : Vec<&dyn Node> = vec![self];
+
+        loop {
+            let current = *chain.last().unwrap();
+            let file = current.get_node_location().map(|location| location.file.clone());
+            let children = current.get_children();
+            let contains = |node: &&dyn Node, strict: bool| match (node.get_node_location(), &file) {
+                (Some(location), Some(file)) if *location.file != **file => false,
+                (Some(location), _) => location.start <= index && (index < location.end || (!strict && index == location.end && location.start < location.end)),
+                (None, _) => false
+            };
+
+            // When the index is between two nodes, the one that starts there is preferred. Empty nodes are ignored.
+            match children.iter().find(|child| contains(child, true)).or_else(|| children.iter().find(|child| contains(child, false))) {
+                Some(child) => chain.push(*child),
+                None => break
+            }
+        }
+
+        match chain[0].get_node_location() {
+            Some(location) if index < location.start || index > location.end => vec![],
+            _ => chain
+        }
+    }
+}
+
+// Field of a `#[parsable]` type, as seen by the generated `Node` implementation. Fields whose type does not implement
+// `Node` (e.g. a type that implements `Parsable` manually) are not children: like strings, they are part of the text
+// of the item that holds them.
+#[doc(hidden)]
+pub struct NodeField<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait NodeFieldImpl<'a> {
+    fn get_field_location(&self) -> Option<&'a ItemLocation>;
+
+    fn collect_field(&self, children: &mut Vec<&'a dyn Node>);
+}
+
+#[doc(hidden)]
+pub trait NodeFieldFallback<'a> {
+    fn get_field_location(&self) -> Option<&'a ItemLocation>;
+
+    fn collect_field(&self, children: &mut Vec<&'a dyn Node>);
+}
+
+impl<'a, T : Node> NodeFieldImpl<'a> for NodeField<'a, T> {
+    fn get_field_location(&self) -> Option<&'a ItemLocation> {
+        self.0.get_node_location()
+    }
+
+    fn collect_field(&self, children: &mut Vec<&'a dyn Node>) {
+        self.0.collect_as_child(children);
+    }
+}
+
+// Only picked when `T` does not implement `Node`, since the method takes one more reference
+impl<'a, T> NodeFieldFallback<'a> for &NodeField<'a, T> {
+    fn get_field_location(&self) -> Option<&'a ItemLocation> {
+        None
+    }
+
+    fn collect_field(&self, _children: &mut Vec<&'a dyn Node>) {
+
+    }
+}
+
+// Strings are the text of the item that holds them, not items of their own
+impl Node for String {
+    fn get_node_name(&self) -> String {
+        String::new()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        None
+    }
+
+    fn collect_as_child<'a>(&'a self, _children: &mut Vec<&'a dyn Node>) {
+
+    }
+}
+
+impl Node for () {
+    fn get_node_name(&self) -> String {
+        String::new()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        None
+    }
+
+    fn collect_as_child<'a>(&'a self, _children: &mut Vec<&'a dyn Node>) {
+
+    }
+}
+
+impl<T : Node> Node for Box<T> {
+    fn get_node_name(&self) -> String {
+        self.as_ref().get_node_name()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        self.as_ref().get_node_location()
+    }
+
+    fn collect_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.as_ref().collect_children(children);
+    }
+
+    fn collect_as_child<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.as_ref().collect_as_child(children);
+    }
+}
+
+impl<T : Node> Node for Rc<T> {
+    fn get_node_name(&self) -> String {
+        self.as_ref().get_node_name()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        self.as_ref().get_node_location()
+    }
+
+    fn collect_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.as_ref().collect_children(children);
+    }
+
+    fn collect_as_child<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.as_ref().collect_as_child(children);
+    }
+}
+
+impl<T : Node> Node for Arc<T> {
+    fn get_node_name(&self) -> String {
+        self.as_ref().get_node_name()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        self.as_ref().get_node_location()
+    }
+
+    fn collect_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.as_ref().collect_children(children);
+    }
+
+    fn collect_as_child<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.as_ref().collect_as_child(children);
+    }
+}
+
+impl<T : Node> Node for Option<T> {
+    fn get_node_name(&self) -> String {
+        self.as_ref().map(|item| item.get_node_name()).unwrap_or_default()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        self.as_ref().and_then(|item| item.get_node_location())
+    }
+
+    fn collect_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        if let Some(item) = self {
+            item.collect_children(children);
+        }
+    }
+
+    fn collect_as_child<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        if let Some(item) = self {
+            item.collect_as_child(children);
+        }
+    }
+}
+
+impl<T : Node> Node for Vec<T> {
+    fn get_node_name(&self) -> String {
+        String::new()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        None
+    }
+
+    fn collect_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.collect_as_child(children);
+    }
+
+    fn collect_as_child<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        for item in self {
+            item.collect_as_child(children);
+        }
+    }
+}
+
+impl<T : Node, U : Node> Node for (T, U) {
+    fn get_node_name(&self) -> String {
+        String::new()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        None
+    }
+
+    fn collect_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.collect_as_child(children);
+    }
+
+    fn collect_as_child<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.0.collect_as_child(children);
+        self.1.collect_as_child(children);
+    }
+}
+
+impl Node for DocComment {
+    fn get_node_name(&self) -> String {
+        Self::get_item_name()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        Some(&self.location)
+    }
+}
+
+impl Node for Newline {
+    fn get_node_name(&self) -> String {
+        Self::get_item_name()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        Some(&self.location)
+    }
+}
+
+impl Node for Indent {
+    fn get_node_name(&self) -> String {
+        Self::get_item_name()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        Some(&self.location)
+    }
+}
+
+impl Node for Dedent {
+    fn get_node_name(&self) -> String {
+        Self::get_item_name()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        Some(&self.location)
+    }
+}
+
+impl<T : Parsable + Node + 'static> Node for Include<T> {
+    fn get_node_name(&self) -> String {
+        Self::get_item_name()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        Some(&self.location)
+    }
+
+    fn collect_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.item.collect_as_child(children);
+    }
+}
+
+impl<T : Recover + Node + 'static> Node for Recoverable<T> {
+    fn get_node_name(&self) -> String {
+        T::get_item_name()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        match self {
+            Self::Item(item) => item.get_node_location(),
+            Self::Error(location) => Some(location),
+        }
+    }
+
+    fn collect_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        if let Self::Item(item) = self {
+            item.collect_children(children);
+        }
+    }
+
+    fn collect_as_child<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            Self::Item(item) => item.collect_as_child(children),
+            Self::Error(_) => children.push(self),
+        }
+    }
+}
+
+impl<T : Parsable + Node + 'static, O : ExpressionOperator + Node + 'static> Node for Expression<T, O> {
+    fn get_node_name(&self) -> String {
+        match self {
+            Self::Operand(operand) => operand.get_node_name(),
+            Self::Prefix { .. } => "prefix expression".to_string(),
+            Self::Postfix { .. } => "postfix expression".to_string(),
+            Self::Binary { .. } => "binary expression".to_string(),
+        }
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        match self {
+            Self::Operand(operand) => operand.get_node_location(),
+            _ => Some(self.location()),
+        }
+    }
+
+    fn collect_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            Self::Operand(operand) => operand.collect_children(children),
+            Self::Prefix { operator, operand, .. } => {
+                operator.collect_as_child(children);
+                operand.collect_as_child(children);
+            },
+            Self::Postfix { operand, operator, .. } => {
+                operand.collect_as_child(children);
+                operator.collect_as_child(children);
+            },
+            Self::Binary { left, operator, right, .. } => {
+                left.collect_as_child(children);
+                operator.collect_as_child(children);
+                right.collect_as_child(children);
+            },
+        }
+    }
+
+    fn collect_as_child<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            Self::Operand(operand) => operand.collect_as_child(children),
+            _ => children.push(self),
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{ParseError, string_reader::StringReader, end_of_file::EndOfFile, ItemLocation, ParseOptions};
+use std::{fs, path::Path};
+use crate::{ParseError, ParseErrorKind, string_reader::StringReader, end_of_file::EndOfFile, ItemLocation, ParseOptions, TriviaList, FileInfo, SharedFileInfo, utils::normalize_path, completion::{Completion, complete_at}, recoverable::RecoveryTokens};
 
 pub trait Parsable : Sized {
     fn parse_item(reader: &mut StringReader) -> Option<Self>;
@@ -13,6 +14,12 @@ pub trait Parsable : Sized {
         unimplemented!()
     }
 
+    // Only implemented by lists of `Recoverable<T>` and by `Recoverable<T>`, to recover with the tokens of a field
+    #[allow(unused_variables)]
+    fn parse_item_with_recovery_tokens(reader: &mut StringReader, separator: Option<&'static str>, tokens: RecoveryTokens) -> Option<Self> {
+        unimplemented!()
+    }
+
     fn location(&self) -> &ItemLocation {
         panic!("type {} has no location", std::any::type_name::<Self>());
     }
@@ -21,6 +28,16 @@ pub trait Parsable : Sized {
         &[]
     }
 
+    // Copy of the item reused by the memoization table and when a left recursion is grown. Items that cannot be
+    // copied are parsed again instead of being reused, and their left-recursive alternatives fail.
+    fn clone_item(&self) -> Option<Self> {
+        None
+    }
+
+    fn complete_at(content: String, cursor: usize, options: ParseOptions) -> Completion where Self : 'static {
+        complete_at::<Self>(content, cursor, options)
+    }
+
     fn get_item_name() -> String;
 
     // fn get_wrapped_name() -> String {
@@ -38,23 +55,112 @@ pub trait Parsable : Sized {
     //     }
     // }
 
-    fn parse(string: String, options: ParseOptions) -> Result<Self, ParseError> {
+    fn parse(string: String, options: ParseOptions) -> Result<Self, ParseError> where Self : 'static {
+        Self::parse_with_trivia(string, options).map(|(value, _)| value)
+    }
+
+    fn parse_file<P : AsRef<Path>>(path: P, options: ParseOptions) -> Result<Self, ParseError> where Self : 'static {
+        let file = read_file(path.as_ref(), options.package_root_path.as_deref().unwrap_or_default())?;
+
+        parse_reader(StringReader::from_file(file, options)).map(|(value, _)| value)
+    }
+
+    fn parse_with_trivia(string: String, options: ParseOptions) -> Result<(Self, TriviaList), ParseError> where Self : 'static {
+        parse_reader(StringReader::new(string, options))
+    }
+
+    fn parse_with_recovery(string: String, options: ParseOptions) -> (Option<Self>, Vec<ParseError>) where Self : 'static {
         let mut reader = StringReader::new(string, options);
 
+        reader.set_recovery_enabled(true);
         reader.eat_spaces();
 
-        match Self::parse_item(&mut reader) {
-            Some(value) => match reader.is_finished() {
-                true => Ok(value),
-                false => {
-                    reader.set_expected_item::<EndOfFile>();
-                    Err(reader.get_error())
-                }
+        let result = reader.parse::<Self>();
+
+        let failure = match &result {
+            Some(_) if reader.is_finished() => None,
+            Some(_) => {
+                reader.set_expected_item::<EndOfFile>();
+                Some(get_failure_error(&reader))
             },
             None => {
                 reader.set_expected_item::<Self>();
-                Err(reader.get_error())
+                Some(get_failure_error(&reader))
+            }
+        };
+
+        let mut errors = reader.take_errors();
+
+        errors.extend(failure);
+
+        if let Some(error) = reader.get_lexical_error() {
+            errors.push(error);
+        }
+
+        (result, errors)
+    }
+}
+
+// The content of a file that is not valid UTF-8 is converted lossily, so that the error can be displayed
+pub(crate) fn read_file(path: &Path, package_root_path: &str) -> Result<SharedFileInfo, ParseError> {
+    let package_root_path = package_root_path.to_string();
+    let file_path = normalize_path(path, Path::new(&package_root_path));
+    let make_error = |kind, content, index| ParseError {
+        kind,
+        file: SharedFileInfo::new(FileInfo::new(content, file_path.clone(), package_root_path.clone())),
+        index,
+        expected: vec![],
+    };
+
+    let mut bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(error) => return Err(make_error(ParseErrorKind::Io(error.kind()), String::new(), 0))
+    };
+
+    if bytes.starts_with(b"\xEF\xBB\xBF") {
+        bytes.drain(..3);
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(content) => Ok(SharedFileInfo::new(FileInfo::new(content, file_path, package_root_path))),
+        Err(error) => {
+            let index = error.utf8_error().valid_up_to();
+            let content = String::from_utf8_lossy(error.as_bytes()).to_string();
+
+            Err(make_error(ParseErrorKind::InvalidUtf8, content, index))
+        }
+    }
+}
+
+// Parses the whole content of the reader as a `T`
+pub(crate) fn parse_reader<T : Parsable + 'static>(mut reader: StringReader) -> Result<(T, TriviaList), ParseError> {
+    reader.eat_spaces();
+
+    let result = reader.parse::<T>();
+
+    if let Some(error) = reader.get_lexical_error() {
+        return Err(error);
+    }
+
+    match result {
+        Some(value) => match reader.is_finished() {
+            true => Ok((value, reader.take_trivia())),
+            false => {
+                reader.set_expected_item::<EndOfFile>();
+                Err(get_failure_error(&reader))
             }
+        },
+        None => {
+            reader.set_expected_item::<T>();
+            Err(get_failure_error(&reader))
         }
     }
+}
+
+// A lexical error discarded where parsing stopped explains the failure better than the expected items
+fn get_failure_error(reader: &StringReader) -> ParseError {
+    match reader.get_discarded_lexical_error() {
+        Some(error) => error,
+        None => reader.get_error()
+    }
 }
\ No newline at end of file
@@ -1,9 +1,31 @@
-use std::rc::Rc;
-use crate::file_info::FileInfo;
+use std::fmt::Display;
+use crate::{file_info::SharedFileInfo, error_renderer::ErrorRenderer};
 
 #[derive(Debug, Clone)]
 pub struct ParseError {
-    pub file: Rc<FileInfo>,
+    pub kind: ParseErrorKind,
+    pub file: SharedFileInfo,
     pub index: usize,
     pub expected: Vec<String>
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnexpectedInput,
+    UnterminatedComment,
+    InconsistentIndentation,
+    InvalidRegex(&'static str),
+    Io(std::io::ErrorKind),
+    InvalidUtf8,
+    IncludeCycle(String)
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", ErrorRenderer::default().render(self))
+    }
+}
+
+impl std::error::Error for ParseError {
+
+}
@@ -0,0 +1,31 @@
+use std::{path::Path, sync::{atomic::{AtomicUsize, Ordering}, Mutex}, thread};
+use crate::{Parsable, ParseOptions, ParseError};
+
+// Files are distributed among a pool of worker threads, each one picking the next unparsed file
+pub fn parse_files<T, P>(paths: &[P], options: ParseOptions) -> Vec<Result<T, ParseError>>
+    where T : Parsable + Send + 'static, P : AsRef<Path> + Sync
+{
+    let next_index = AtomicUsize::new(0);
+    let results : Mutex<Vec<Option<Result<T, ParseError>>>> = Mutex::new(paths.iter().map(|_| None).collect());
+    let thread_count = thread::available_parallelism().map(|count| count.get()).unwrap_or(1).min(paths.len());
+
+    thread::scope(|scope| {
+        for _ in 0..thread_count {
+            scope.spawn(|| {
+                loop {
+                    let index = next_index.fetch_add(1, Ordering::Relaxed);
+
+                    if index >= paths.len() {
+                        break;
+                    }
+
+                    let result = T::parse_file(&paths[index], options.clone());
+
+                    results.lock().unwrap_or_else(|error| error.into_inner())[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    results.into_inner().unwrap_or_else(|error| error.into_inner()).into_iter().map(|result| result.unwrap()).collect()
+}
@@ -1,6 +1,37 @@
-#[derive(Default)]
+const DEFAULT_WHITESPACE: &str = " \t\r\n";
+
+#[derive(Default, Clone)]
 pub struct ParseOptions {
     pub file_path: Option<String>,
     pub package_root_path: Option<String>,
-    pub comment_start: Option<&'static str>
-}
\ No newline at end of file
+    pub whitespace: Option<&'static str>,
+    pub comment_start: Option<&'static str>,
+    pub comments: Vec<CommentSyntax>,
+    pub doc_comment_start: Option<&'static str>,
+    pub memoize: bool,
+    pub indentation_sensitive: bool,
+    pub collect_comments: bool,
+    pub collect_whitespace: bool
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentSyntax {
+    Line(&'static str),
+    Block(&'static str, &'static str),
+    NestedBlock(&'static str, &'static str)
+}
+
+impl ParseOptions {
+    pub(crate) fn get_whitespace(&self) -> &'static str {
+        self.whitespace.unwrap_or(DEFAULT_WHITESPACE)
+    }
+
+    // `comment_start` is a shorthand for a line comment syntax, which is tried first
+    pub(crate) fn get_comment_syntax(&self, index: usize) -> Option<CommentSyntax> {
+        match self.comment_start {
+            Some(start) if index == 0 => Some(CommentSyntax::Line(start)),
+            Some(_) => self.comments.get(index - 1).copied(),
+            None => self.comments.get(index).copied()
+        }
+    }
+}
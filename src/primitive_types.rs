@@ -1,4 +1,5 @@
-use crate::{parsable::Parsable, string_reader::StringReader, ItemLocation};
+use std::{rc::Rc, sync::Arc};
+use crate::{parsable::Parsable, string_reader::StringReader, ItemLocation, recoverable::RecoveryTokens};
 
 impl Parsable for () {
     fn parse_item(_reader: &mut StringReader) -> Option<Self> {
@@ -8,15 +9,19 @@ impl Parsable for () {
     fn get_item_name() -> String {
         "()".to_string()
     }
+
+    fn clone_item(&self) -> Option<Self> {
+        Some(())
+    }
 }
 
-impl<T : Parsable> Parsable for Box<T> {
+impl<T : Parsable + 'static> Parsable for Box<T> {
     fn get_item_name() -> String {
         <T as Parsable>::get_item_name()
     }
 
     fn parse_item(reader: &mut StringReader) -> Option<Self> {
-        match <T as Parsable>::parse_item(reader) {
+        match reader.parse::<T>() {
             Some(value) => Some(Box::new(value)),
             None => None
         }
@@ -25,15 +30,56 @@ impl<T : Parsable> Parsable for Box<T> {
     fn location(&self) -> &ItemLocation {
         Box::as_ref(self).location()
     }
+
+    fn clone_item(&self) -> Option<Self> {
+        Box::as_ref(self).clone_item().map(Box::new)
+    }
 }
 
-impl<T : Parsable> Parsable for Option<T> {
+impl<T : Parsable + 'static> Parsable for Rc<T> {
     fn get_item_name() -> String {
         <T as Parsable>::get_item_name()
     }
 
     fn parse_item(reader: &mut StringReader) -> Option<Self> {
-        match <T as Parsable>::parse_item(reader) {
+        reader.parse::<T>().map(Rc::new)
+    }
+
+    fn location(&self) -> &ItemLocation {
+        Rc::as_ref(self).location()
+    }
+
+    // Shared items can always be reused, even if `T` cannot be cloned
+    fn clone_item(&self) -> Option<Self> {
+        Some(Rc::clone(self))
+    }
+}
+
+impl<T : Parsable + 'static> Parsable for Arc<T> {
+    fn get_item_name() -> String {
+        <T as Parsable>::get_item_name()
+    }
+
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        reader.parse::<T>().map(Arc::new)
+    }
+
+    fn location(&self) -> &ItemLocation {
+        Arc::as_ref(self).location()
+    }
+
+    fn clone_item(&self) -> Option<Self> {
+        Some(Arc::clone(self))
+    }
+}
+
+impl<T : Parsable + 'static> Parsable for Option<T> {
+    fn get_item_name() -> String {
+        <T as Parsable>::get_item_name()
+    }
+
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        match reader.parse::<T>() {
             Some(value) => Some(Some(value)),
             None => {
                 reader.set_expected_item::<T>();
@@ -41,14 +87,32 @@ impl<T : Parsable> Parsable for Option<T> {
             }
         }
     }
+
+    fn clone_item(&self) -> Option<Self> {
+        match self {
+            Some(item) => item.clone_item().map(Some),
+            None => Some(None)
+        }
+    }
 }
 
-impl<T : Parsable> Parsable for Vec<T> {
+impl<T : Parsable + 'static> Parsable for Vec<T> {
     fn parse_item(reader: &mut StringReader) -> Option<Self> {
         let mut result = vec![];
 
-        while let Some(value) = T::parse_item(reader) {
-            result.push(value);
+        loop {
+            let start = reader.get_index();
+
+            match reader.parse::<T>() {
+                Some(value) => result.push(value),
+                None => break
+            }
+
+            // An item that does not consume any input (e.g. `Newline` at the end of the input) would be matched forever
+            if reader.get_index() == start {
+                break;
+            }
+
             reader.eat_spaces();
         }
 
@@ -58,8 +122,17 @@ impl<T : Parsable> Parsable for Vec<T> {
     fn parse_item_without_consuming_spaces(reader: &mut StringReader) -> Option<Self> {
         let mut result = vec![];
 
-        while let Some(value) = T::parse_item(reader) {
-            result.push(value);
+        loop {
+            let start = reader.get_index();
+
+            match reader.parse::<T>() {
+                Some(value) => result.push(value),
+                None => break
+            }
+
+            if reader.get_index() == start {
+                break;
+            }
         }
 
         Some(result)
@@ -69,10 +142,14 @@ impl<T : Parsable> Parsable for Vec<T> {
         <T as Parsable>::get_item_name()
     }
 
+    fn clone_item(&self) -> Option<Self> {
+        self.iter().map(|item| item.clone_item()).collect()
+    }
+
     fn parse_item_with_separator(reader: &mut StringReader, separator: &'static str) -> Option<Self> {
         let mut result = vec![];
 
-        while let Some(value) = T::parse_item(reader) {
+        while let Some(value) = reader.parse::<T>() {
             result.push(value);
             reader.eat_spaces();
 
@@ -87,23 +164,54 @@ impl<T : Parsable> Parsable for Vec<T> {
 
         Some(result)
     }
+
+    fn parse_item_with_recovery_tokens(reader: &mut StringReader, separator: Option<&'static str>, tokens: RecoveryTokens) -> Option<Self> {
+        let mut result = vec![];
+
+        loop {
+            let start = reader.get_index();
+
+            match T::parse_item_with_recovery_tokens(reader, None, tokens) {
+                Some(value) => result.push(value),
+                None => break
+            }
+
+            if reader.get_index() == start {
+                break;
+            }
+
+            reader.eat_spaces();
+
+            if let Some(separator) = separator {
+                match reader.read_string(separator) {
+                    Some(_) => reader.eat_spaces(),
+                    None => {
+                        reader.set_expected_string(separator);
+                        break;
+                    }
+                }
+            }
+        }
+
+        Some(result)
+    }
 }
 
-impl<T : Parsable, U : Parsable> Parsable for (T, U) {
+impl<T : Parsable + 'static, U : Parsable + 'static> Parsable for (T, U) {
     fn get_item_name() -> String {
         format!("({}, {})", <T as Parsable>::get_item_name(), <U as Parsable>::get_item_name())
     }
 
     fn parse_item(reader: &mut StringReader) -> Option<Self> {
         let start_index = reader.get_index();
-        let first = match T::parse_item(reader) {
+        let first = match reader.parse::<T>() {
             Some(value) => value,
             None => {
                 reader.set_expected_item::<T>();
                 return None;
             }
         };
-        let second = match U::parse_item(reader) {
+        let second = match reader.parse::<U>() {
             Some(value) => value,
             None => {
                 reader.set_expected_item::<U>();
@@ -114,4 +222,8 @@ impl<T : Parsable, U : Parsable> Parsable for (T, U) {
 
         Some((first, second))
     }
+
+    fn clone_item(&self) -> Option<Self> {
+        Some((self.0.clone_item()?, self.1.clone_item()?))
+    }
 }
\ No newline at end of file
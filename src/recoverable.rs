@@ -0,0 +1,120 @@
+use crate::{Parsable, StringReader, ItemLocation};
+
+pub trait Recover : Parsable {
+    // Tokens that end a failed item, consumed along with it
+    fn get_recovery_terminators() -> &'static[&'static str] {
+        &[]
+    }
+
+    // Tokens that end a failed item, left for the following items
+    fn get_recovery_delimiters() -> &'static[&'static str] {
+        &[]
+    }
+}
+
+// Synchronization tokens of a `Recoverable<T>` field, which replace the ones of `T`
+#[derive(Debug, Clone, Copy)]
+pub struct RecoveryTokens {
+    pub terminators: &'static[&'static str],
+    pub delimiters: &'static[&'static str]
+}
+
+impl RecoveryTokens {
+    pub fn of<T : Recover>() -> Self {
+        Self {
+            terminators: T::get_recovery_terminators(),
+            delimiters: T::get_recovery_delimiters()
+        }
+    }
+
+    fn is_at_delimiter(&self, reader: &StringReader) -> bool {
+        self.delimiters.iter().any(|delimiter| reader.as_str().starts_with(delimiter))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Recoverable<T> {
+    Item(T),
+    Error(ItemLocation)
+}
+
+impl<T : Recover + 'static> Recoverable<T> {
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Error(_))
+    }
+
+    pub fn as_item(&self) -> Option<&T> {
+        match self {
+            Self::Item(item) => Some(item),
+            Self::Error(_) => None,
+        }
+    }
+
+    fn skip_failed_item(reader: &mut StringReader, tokens: RecoveryTokens) {
+        while !reader.is_finished() {
+            if tokens.is_at_delimiter(reader) {
+                return;
+            }
+
+            for terminator in tokens.terminators {
+                if reader.read_string(terminator).is_some() {
+                    return;
+                }
+            }
+
+            let char_length = reader.as_str().chars().next().map(|c| c.len_utf8()).unwrap_or(1);
+
+            reader.advance(char_length);
+        }
+    }
+}
+
+impl<T : Recover + 'static> Parsable for Recoverable<T> {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        Self::parse_item_with_recovery_tokens(reader, None, RecoveryTokens::of::<T>())
+    }
+
+    fn parse_item_with_recovery_tokens(reader: &mut StringReader, _separator: Option<&'static str>, tokens: RecoveryTokens) -> Option<Self> {
+        if let Some(item) = reader.parse::<T>() {
+            return Some(Self::Item(item));
+        }
+
+        let start = reader.get_index();
+
+        if !reader.is_recovery_enabled() || reader.is_finished() || tokens.is_at_delimiter(reader) {
+            return None;
+        }
+
+        reader.set_expected_item::<T>();
+        reader.register_error(start);
+        Self::skip_failed_item(reader, tokens);
+
+        let location = reader.get_item_location(start);
+
+        reader.eat_spaces();
+
+        Some(Self::Error(location))
+    }
+
+    fn get_item_name() -> String {
+        T::get_item_name()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        match self {
+            Self::Item(item) => item.location(),
+            Self::Error(location) => location,
+        }
+    }
+
+    fn get_completion_suggestions() -> &'static[&'static str] {
+        T::get_completion_suggestions()
+    }
+
+    fn clone_item(&self) -> Option<Self> {
+        match self {
+            Self::Item(item) => item.clone_item().map(Self::Item),
+            Self::Error(location) => Some(Self::Error(location.clone())),
+        }
+    }
+}
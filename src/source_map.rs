@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use crate::{Parsable, ParseOptions, ParseError, FileInfo, SharedFileInfo, ItemLocation, TriviaList, StringReader, parsable::parse_reader};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(pub usize);
+
+// Owns the files of a project. Each file keeps the same id when its content is replaced,
+// so that locations from successive parses of the same file are equal.
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SharedFileInfo>,
+    ids_by_path: HashMap<String, FileId>
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_file(&mut self, content: String, path: String, package_root_path: String) -> FileId {
+        match self.ids_by_path.get(&path) {
+            Some(id) => {
+                self.files[id.0] = SharedFileInfo::new(FileInfo::new(content, path, package_root_path).with_id(*id));
+
+                *id
+            },
+            None => {
+                let id = FileId(self.files.len());
+
+                self.ids_by_path.insert(path.clone(), id);
+                self.files.push(SharedFileInfo::new(FileInfo::new(content, path, package_root_path).with_id(id)));
+
+                id
+            }
+        }
+    }
+
+    pub fn get_file(&self, id: FileId) -> Option<&SharedFileInfo> {
+        self.files.get(id.0)
+    }
+
+    pub fn get_file_id(&self, path: &str) -> Option<FileId> {
+        self.ids_by_path.get(path).cloned()
+    }
+
+    pub fn get_location(&self, id: FileId, start: usize, end: usize) -> Option<ItemLocation> {
+        let file = self.get_file(id)?;
+
+        match start <= end && end <= file.content.len() {
+            true => Some(ItemLocation { file: file.clone(), start, end }),
+            false => None
+        }
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &SharedFileInfo> {
+        self.files.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn parse<T : Parsable + 'static>(&self, id: FileId, options: ParseOptions) -> Option<Result<T, ParseError>> {
+        self.parse_with_trivia(id, options).map(|result| result.map(|(value, _)| value))
+    }
+
+    pub fn parse_with_trivia<T : Parsable + 'static>(&self, id: FileId, options: ParseOptions) -> Option<Result<(T, TriviaList), ParseError>> {
+        let file = self.get_file(id)?.clone();
+
+        Some(parse_reader(StringReader::from_file(file, options)))
+    }
+}
@@ -1,42 +1,96 @@
-use std::{collections::HashMap, rc::Rc, ptr};
+use std::{any::{Any, TypeId}, collections::HashMap, sync::{OnceLock, Mutex}, path::{Path, PathBuf}};
 use regex::Regex;
-use crate::{ItemLocation, file_info::FileInfo, Parsable, marker_list::MarkerList, ParseOptions};
-use super::parse_error::ParseError;
+use unicode_xid::UnicodeXID;
+use crate::{ItemLocation, file_info::{FileInfo, SharedFileInfo}, Parsable, marker_list::MarkerList, memo_table::{MemoTable, MemoEntry, ExpectedTrace, ParseFrame, ParseState, ReaderState}, ParseOptions, parse_options::CommentSyntax, trivia::{Trivia, TriviaKind, TriviaList}, indentation::{IndentationStack, IndentationComparison}, parsable::{read_file, parse_reader}, utils::{remove_dot_components, normalize_path}, completion::CompletionItem, recoverable::RecoveryTokens};
+use super::parse_error::{ParseError, ParseErrorKind};
+
+const SIGNIFICANT_NEWLINES_MARKER: &str = "significant_newlines";
 
 pub struct StringReader {
-    comment_token: Option<&'static str>,
-    file: Rc<FileInfo>,
+    options: ParseOptions,
+    include_stack: Vec<PathBuf>,
+    lexical_error: Option<LexicalError>,
+    discarded_lexical_error: Option<LexicalError>,
+    indentation: Option<IndentationStack>,
+    file: SharedFileInfo,
     index: usize,
     error_index: usize,
     expected: Vec<String>,
-    markers: MarkerList
+    markers: MarkerList,
+    memo_table: Option<MemoTable>,
+    frames: Vec<ParseFrame>,
+    frame_count: usize,
+    expected_traces: Vec<ExpectedTrace>,
+    completion_index: Option<usize>,
+    completion_items: Vec<CompletionItem>,
+    recovery_enabled: bool,
+    errors: Vec<(usize, ParseError)>,
+    trivia: TriviaList,
+    last_eaten_spaces: Option<(usize, usize)>,
+    last_eaten_comments: Vec<(CommentSyntax, usize, usize)>,
+    regexes: HashMap<&'static str, Option<&'static Regex>>
 }
 
-static mut REGEXES : Option<HashMap<&'static str, Regex>> = None;
+// Lexical error along with the item being parsed and the index when it was set.
+// It is discarded if that item fails or backtracks before the index.
+#[derive(Clone)]
+pub(crate) struct LexicalError {
+    error: ParseError,
+    index: usize,
+    frame_id: usize
+}
 
-fn get_regex(pattern: &'static str) -> &'static Regex {
-    let regexes_opt = unsafe { &mut *ptr::addr_of_mut!(REGEXES) };
-    let regexes = regexes_opt.get_or_insert(HashMap::new());
+// Compiled regexes are shared by all readers. Invalid patterns are cached as `None` so they are only compiled once.
+// Patterns come from the grammar, so there is a bounded number of them: they are leaked to be used without the lock,
+// and each reader keeps its own copy of the map so that the lock is only taken once per pattern.
+static REGEXES : OnceLock<Mutex<HashMap<&'static str, Option<&'static Regex>>>> = OnceLock::new();
 
-    if !regexes.contains_key(pattern) {
-        regexes.insert(pattern, Regex::new(&format!("^({})", pattern)).unwrap());
-    }
+fn get_regex(pattern: &'static str) -> Option<&'static Regex> {
+    let mut regexes = REGEXES.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap_or_else(|error| error.into_inner());
 
-    regexes.get(pattern).unwrap()
+    *regexes.entry(pattern).or_insert_with(|| {
+        Regex::new(&format!("^({})", pattern)).ok().map(|regex| &*Box::leak(Box::new(regex)))
+    })
 }
 
 impl StringReader {
     pub fn new(content: String, options: ParseOptions) -> Self {
-        let path = options.file_path.unwrap_or_default();
-        let package_root_path = options.package_root_path.unwrap_or_default();
+        let path = options.file_path.clone().unwrap_or_default();
+        let package_root_path = options.package_root_path.clone().unwrap_or_default();
+
+        Self::from_file(SharedFileInfo::new(FileInfo::new(content, path, package_root_path)), options)
+    }
+
+    // The `file_path` and `package_root_path` options are ignored, the ones of `file` are used instead
+    pub fn from_file(file: SharedFileInfo, options: ParseOptions) -> Self {
+        let include_stack = match file.path.is_empty() {
+            true => vec![],
+            false => vec![remove_dot_components(&Path::new(&file.package_root_path).join(&file.path))]
+        };
 
         Self {
-            comment_token: options.comment_start,
-            file: Rc::new(FileInfo::new(content, path, package_root_path)),
+            include_stack,
+            lexical_error: None,
+            discarded_lexical_error: None,
+            indentation: options.indentation_sensitive.then(IndentationStack::new),
+            file,
             index: 0,
             error_index: 0,
             expected: vec![],
-            markers: MarkerList::new()
+            markers: MarkerList::new(),
+            memo_table: options.memoize.then(MemoTable::new),
+            frames: vec![],
+            frame_count: 0,
+            expected_traces: vec![],
+            completion_index: None,
+            completion_items: vec![],
+            recovery_enabled: false,
+            errors: vec![],
+            trivia: TriviaList::new(),
+            last_eaten_spaces: None,
+            last_eaten_comments: vec![],
+            regexes: HashMap::new(),
+            options
         }
     }
 
@@ -50,45 +104,186 @@ impl StringReader {
 
     pub fn set_expected_string(&mut self, expected: &'static str) {
         if !expected.is_empty() {
+            self.add_completion_item(|| CompletionItem::literal(expected));
             self.set_expected_entity(format!("\"{}\"", expected));
         }
     }
 
     pub fn set_expected_item<T : Parsable>(&mut self) {
+        for suggestion in T::get_completion_suggestions() {
+            self.add_completion_item(|| CompletionItem::suggestion(suggestion, T::get_item_name()));
+        }
+
         self.set_expected_entity(T::get_item_name());
     }
 
+    fn add_completion_item(&mut self, make_item: impl FnOnce() -> CompletionItem) {
+        if self.completion_index == Some(self.index) {
+            let item = make_item();
+
+            if let Some(trace) = self.expected_traces.last_mut() {
+                trace.completion_items.push(item.clone());
+            }
+
+            self.completion_items.push(item);
+        }
+    }
+
+    // When set, the items that would be accepted at this index are recorded as completion candidates
+    pub fn set_completion_index(&mut self, index: Option<usize>) {
+        self.completion_index = index;
+    }
+
+    pub fn take_completion_items(&mut self) -> Vec<CompletionItem> {
+        std::mem::take(&mut self.completion_items)
+    }
+
     fn set_expected_entity(&mut self, string_to_display: String) {
-        if self.index == self.error_index {
-            self.expected.push(string_to_display);
-        } else if self.index > self.error_index {
+        self.set_expected_entity_at(self.index, string_to_display);
+    }
+
+    fn set_expected_entity_at(&mut self, index: usize, string_to_display: String) {
+        if let Some(trace) = self.expected_traces.last_mut() {
+            trace.record(index, string_to_display.clone());
+        }
+
+        if index == self.error_index {
+            if !self.expected.contains(&string_to_display) {
+                self.expected.push(string_to_display);
+            }
+        } else if index > self.error_index {
             self.expected = vec![string_to_display];
-            self.error_index = self.index;
+            self.error_index = index;
         }
     }
 
     pub fn get_error(&self) -> ParseError {
-        let mut error_index = self.error_index;
-        let mut backtracked = false;
-
-        while error_index > 0 && is_space(self.content().as_bytes()[error_index - 1] as char) {
-            error_index -= 1;
-            backtracked = true;
-        }
+        let mut error_index = self.skip_whitespace_backward(self.error_index);
 
-        if backtracked {
-            while error_index < self.content().len() && is_inline_space(self.content().as_bytes()[error_index] as char) {
-                error_index += 1;
+        if error_index < self.error_index {
+            while let Some(c) = self.content()[error_index..].chars().next().filter(|c| self.is_whitespace(*c) && !is_line_break(*c)) {
+                error_index += c.len_utf8();
             }
         }
 
         ParseError {
+            kind: ParseErrorKind::UnexpectedInput,
             file: self.file.clone(),
             index: error_index,
             expected: self.expected.clone(),
         }
     }
 
+    pub fn set_lexical_error(&mut self, kind: ParseErrorKind, index: usize) {
+        self.set_nested_error(ParseError {
+            kind,
+            file: self.file.clone(),
+            index,
+            expected: vec![],
+        });
+    }
+
+    // Errors from other files, e.g. included files, abort parsing as well
+    pub fn set_nested_error(&mut self, error: ParseError) {
+        let index = self.index;
+
+        self.keep_lexical_error(error, index);
+    }
+
+    // Errors that abort parsing regardless of the grammar rules, e.g. an unterminated comment or an invalid regex.
+    // An error set in a branch that backtracks is discarded, it is only reported if parsing fails at that point.
+    pub fn get_lexical_error(&self) -> Option<ParseError> {
+        self.lexical_error.as_ref().map(|lexical_error| lexical_error.error.clone())
+    }
+
+    pub(crate) fn get_discarded_lexical_error(&self) -> Option<ParseError> {
+        match &self.discarded_lexical_error {
+            Some(lexical_error) if lexical_error.index >= self.error_index => Some(lexical_error.error.clone()),
+            _ => None
+        }
+    }
+
+    fn keep_lexical_error(&mut self, error: ParseError, index: usize) {
+        if self.lexical_error.is_none() {
+            self.lexical_error = Some(LexicalError { error, index, frame_id: self.get_frame_id() });
+        }
+    }
+
+    // Only the errors set by the given frame (or the frames it started) after the index are discarded
+    fn discard_lexical_error(&mut self, frame_id: usize, index: usize) {
+        if !self.lexical_error.as_ref().is_some_and(|lexical_error| lexical_error.frame_id >= frame_id && lexical_error.index >= index) {
+            return;
+        }
+
+        let lexical_error = self.lexical_error.take().unwrap();
+
+        if self.discarded_lexical_error.as_ref().is_none_or(|discarded| discarded.index <= lexical_error.index) {
+            self.discarded_lexical_error = Some(lexical_error);
+        }
+    }
+
+    fn get_frame_id(&self) -> usize {
+        self.frames.last().map(|frame| frame.id).unwrap_or(0)
+    }
+
+    pub fn get_options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    // Included files are searched relatively to the current file, then to the package root
+    fn resolve_include_path(&self, path: &str) -> PathBuf {
+        let root_path = Path::new(&self.file.package_root_path);
+        let current_path = root_path.join(&self.file.path);
+        let candidate = current_path.parent().unwrap_or(Path::new("")).join(path);
+
+        match candidate.exists() || self.file.package_root_path.is_empty() {
+            true => remove_dot_components(&candidate),
+            false => remove_dot_components(&root_path.join(path))
+        }
+    }
+
+    // Parses another file with the same options, in the package of the current file. `index` is the location of the include directive, used to report cycles.
+    pub fn parse_included_file<T : Parsable + 'static>(&self, path: &str, index: usize) -> Result<T, ParseError> {
+        let path = self.resolve_include_path(path);
+
+        if self.include_stack.contains(&path) {
+            return Err(ParseError {
+                kind: ParseErrorKind::IncludeCycle(normalize_path(&path, Path::new(&self.file.package_root_path))),
+                file: self.file.clone(),
+                index,
+                expected: vec![],
+            });
+        }
+
+        let file = read_file(&path, &self.file.package_root_path)?;
+        let mut reader = StringReader::from_file(file, self.options.clone());
+
+        reader.include_stack = self.include_stack.clone();
+        reader.include_stack.push(path);
+
+        parse_reader(reader).map(|(value, _)| value)
+    }
+
+    pub fn is_recovery_enabled(&self) -> bool {
+        self.recovery_enabled
+    }
+
+    pub fn set_recovery_enabled(&mut self, value: bool) {
+        self.recovery_enabled = value;
+    }
+
+    pub fn register_error(&mut self, start: usize) {
+        let error = self.get_error();
+
+        self.errors.push((start, error));
+        self.expected.clear();
+        self.error_index = self.index;
+    }
+
+    pub fn take_errors(&mut self) -> Vec<ParseError> {
+        self.errors.drain(..).map(|(_, error)| error).collect()
+    }
+
     pub fn is_finished(&self) -> bool {
         self.index == self.file.content.len()
     }
@@ -100,9 +295,18 @@ impl StringReader {
     pub fn get_index_backtracked(&self) -> usize {
         let mut index = self.index;
 
-        // TODO: handle comments
-        while index > 0 && is_space(self.content().as_bytes()[index - 1] as char) {
-            index -= 1;
+        if let Some((start, end)) = self.last_eaten_spaces {
+            if end == index {
+                index = start;
+            }
+        }
+
+        self.skip_whitespace_backward(index)
+    }
+
+    fn skip_whitespace_backward(&self, mut index: usize) -> usize {
+        while let Some(c) = self.content()[..index].chars().next_back().filter(|c| self.is_whitespace(*c)) {
+            index -= c.len_utf8();
         }
 
         index
@@ -110,6 +314,21 @@ impl StringReader {
 
     pub fn set_index(&mut self, index: usize) {
         self.index = index;
+
+        // Errors recovered after the new index belong to items that are being discarded
+        while self.errors.last().is_some_and(|(error_start, _)| *error_start >= index) {
+            self.errors.pop();
+        }
+
+        self.trivia.truncate(index);
+
+        let frame_id = self.get_frame_id();
+
+        self.discard_lexical_error(frame_id, index);
+
+        if let Some(indentation) = &mut self.indentation {
+            indentation.backtrack(index, frame_id);
+        }
     }
 
     pub fn advance(&mut self, length: usize) -> Option<&str> {
@@ -130,46 +349,338 @@ impl StringReader {
     }
 
     pub fn as_char(&self) -> char {
-        match self.as_str().as_bytes().first() {
-            Some(byte) => *byte as char,
-            None => 0 as char
-        }
+        self.as_str().chars().next().unwrap_or('\0')
     }
 
     pub fn at(&self, index: usize) -> char {
-        match self.content().as_bytes().get(self.index + index) {
-            Some(byte) => *byte as char,
-            None => 0 as char,
+        match self.content().get(self.index + index..) {
+            Some(string) => string.chars().next().unwrap_or('\0'),
+            None => '\0',
         }
     }
 
     pub fn eat_spaces(&mut self) {
-        let mut done = false;
+        let line_sensitive = self.indentation.is_some() && self.are_line_breaks_significant();
 
-        while !done {
-            done = true;
+        // The indentation at the start of a line is significant and left to `Newline`, `Indent` and `Dedent`
+        if line_sensitive && (self.index == 0 || self.content()[..self.index].ends_with('\n')) {
+            if self.index == 0 {
+                self.skip_blank_lines();
+            }
 
-            while is_space(self.as_char()) {
-                self.index += 1;
+            return;
+        }
+
+        self.skip_spaces_and_comments(self.are_line_breaks_significant());
+    }
+
+    // Line breaks are significant if they are not part of the whitespace set, or if the `significant_newlines` marker is set.
+    // In indentation-sensitive mode, unsetting the marker allows expressions to span multiple lines (e.g. inside brackets).
+    pub fn are_line_breaks_significant(&self) -> bool {
+        match self.markers.get_declared(SIGNIFICANT_NEWLINES_MARKER) {
+            Some(value) => value,
+            None => self.indentation.is_some() || !self.options.get_whitespace().contains('\n')
+        }
+    }
+
+    // Unsetting the `significant_newlines` marker skips line breaks even if they are not part of the whitespace set
+    fn is_skipped_space(&self, c: char, line_sensitive: bool) -> bool {
+        match is_line_break(c) {
+            true => !line_sensitive && (self.is_whitespace(c) || self.markers.get_declared(SIGNIFICANT_NEWLINES_MARKER) == Some(false)),
+            false => self.is_whitespace(c)
+        }
+    }
+
+    fn is_whitespace(&self, c: char) -> bool {
+        c != '\0' && self.options.get_whitespace().contains(c)
+    }
+
+    fn skip_spaces_and_comments(&mut self, line_sensitive: bool) {
+        let eat_start = self.index;
+        let mut comments = vec![];
+
+        loop {
+            let start = self.index;
+
+            while self.is_skipped_space(self.as_char(), line_sensitive) {
+                self.index += self.as_char().len_utf8();
+            }
+
+            if self.index > start {
+                self.add_trivia(TriviaKind::Whitespace, start);
+            }
+
+            let comment_start = self.index;
+
+            match self.eat_comment() {
+                Some(syntax) => comments.push((syntax, comment_start, self.index)),
+                None => break
             }
+        }
+
+        if self.index > eat_start {
+            self.last_eaten_spaces = Some((eat_start, self.index));
+            self.last_eaten_comments = comments;
+        }
+    }
 
-            if let Some(comment_token) = self.comment_token {
-                if self.as_str().starts_with(comment_token) {
-                    done = false;
+    fn eat_comment(&mut self) -> Option<CommentSyntax> {
+        let mut i = 0;
 
-                    while self.as_char() != '\n' && self.index < self.content().len() {
-                        self.index += 1;
+        while let Some(syntax) = self.options.get_comment_syntax(i) {
+            let comment_start = self.index;
+
+            match syntax {
+                CommentSyntax::Line(start) => {
+                    if self.as_str().starts_with(start) {
+                        while self.as_char() != '\n' && self.index < self.content().len() {
+                            self.index += self.as_char().len_utf8();
+                        }
+
+                        self.add_trivia(TriviaKind::LineComment, comment_start);
+
+                        return Some(syntax);
                     }
-                }
+                },
+                CommentSyntax::Block(start, end) | CommentSyntax::NestedBlock(start, end) => {
+                    if self.as_str().starts_with(start) {
+                        let nested = matches!(syntax, CommentSyntax::NestedBlock(..));
+
+                        self.eat_block_comment(start, end, nested);
+                        self.add_trivia(TriviaKind::BlockComment, comment_start);
+
+                        return Some(syntax);
+                    }
+                },
+            }
+
+            i += 1;
+        }
+
+        None
+    }
+
+    pub fn is_indentation_sensitive(&self) -> bool {
+        self.indentation.is_some()
+    }
+
+    // Consumes a line break and the blank lines that follow, and returns the indentation of the next line
+    pub fn read_line_break(&mut self) -> Option<String> {
+        self.rewind_to_skipped_line_break();
+
+        let start = self.index;
+
+        self.skip_spaces_and_comments(true);
+
+        // The end of the input ends the last line, unless it has already been ended by a line break
+        if self.is_finished() {
+            if self.content()[..start].trim_end_matches(is_inline_space).ends_with('\n') {
+                self.index = start;
+                return None;
+            }
+
+            return Some(String::new());
+        }
+
+        if !self.read_single_line_break() {
+            self.index = start;
+            return None;
+        }
+
+        loop {
+            let line_start = self.index;
+
+            self.skip_spaces_and_comments(true);
+
+            if self.is_finished() {
+                return Some(String::new());
+            }
+
+            if !self.read_single_line_break() {
+                self.index = line_start;
+
+                return self.get_line_indentation();
+            }
+        }
+    }
+
+    // A line break may have been skipped as whitespace, e.g. at the end of a scope where line breaks are not significant
+    fn rewind_to_skipped_line_break(&mut self) {
+        let (start, end) = match self.last_eaten_spaces {
+            Some((start, end)) if end == self.index => (start, end),
+            _ => return
+        };
+
+        let line_break = self.content()[start..end].char_indices()
+            .map(|(i, c)| (start + i, c))
+            .find(|(i, c)| is_line_break(*c) && !self.last_eaten_comments.iter().any(|(_, comment_start, comment_end)| comment_start <= i && i < comment_end));
+
+        if let Some((index, _)) = line_break {
+            self.set_index(index);
+        }
+    }
+
+    fn skip_blank_lines(&mut self) {
+        loop {
+            let line_start = self.index;
+
+            self.skip_spaces_and_comments(true);
+
+            if self.is_finished() {
+                return;
+            }
+
+            if !self.read_single_line_break() {
+                self.index = line_start;
+                return;
+            }
+        }
+    }
+
+    fn read_single_line_break(&mut self) -> bool {
+        for line_break in ["\r\n", "\n"] {
+            if self.as_str().starts_with(line_break) {
+                self.index += line_break.len();
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // Indentation of the current line, if the current index is inside it
+    pub fn get_line_indentation(&self) -> Option<String> {
+        if self.is_finished() {
+            return Some(String::new());
+        }
+
+        let line_start = self.content()[..self.index].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line = &self.content()[line_start..];
+        let indentation = &line[..line.len() - line.trim_start_matches(is_inline_space).len()];
+
+        match self.index <= line_start + indentation.len() {
+            true => Some(indentation.to_string()),
+            false => None
+        }
+    }
+
+    // Nothing can be read at the start of a line that closes an indentation level until `Dedent` is matched
+    fn is_dedent_pending(&self) -> bool {
+        if self.indentation.is_none() || !self.are_line_breaks_significant() || !(self.index == 0 || self.content()[..self.index].ends_with(['\n', ' ', '\t'])) {
+            return false;
+        }
+
+        match self.get_line_indentation() {
+            Some(indentation) => self.compare_indentation(&indentation) == IndentationComparison::Shallower,
+            None => false
+        }
+    }
+
+    pub fn skip_indentation(&mut self) {
+        while is_inline_space(self.as_char()) {
+            self.index += 1;
+        }
+    }
+
+    pub fn compare_indentation(&self, indentation: &str) -> IndentationComparison {
+        match &self.indentation {
+            Some(stack) => stack.compare(indentation),
+            None => IndentationComparison::Same
+        }
+    }
+
+    pub fn push_indentation(&mut self, indentation: String, index: usize) {
+        if let Some(stack) = &mut self.indentation {
+            stack.push(indentation, index);
+        }
+    }
+
+    pub fn pop_indentation(&mut self, index: usize) {
+        let frame_id = self.get_frame_id();
+
+        if let Some(stack) = &mut self.indentation {
+            stack.pop(index, frame_id);
+        }
+    }
+
+    // Comments skipped just before the current index, if any
+    pub fn get_preceding_comments(&self) -> Vec<(CommentSyntax, ItemLocation)> {
+        match self.last_eaten_spaces {
+            Some((_, end)) if end == self.index => self.last_eaten_comments.iter().map(|(syntax, start, end)| {
+                let location = ItemLocation {
+                    file: self.file.clone(),
+                    start: *start,
+                    end: *end,
+                };
+
+                (*syntax, location)
+            }).collect(),
+            _ => vec![]
+        }
+    }
+
+    pub fn get_doc_comment_start(&self) -> Option<&'static str> {
+        self.options.doc_comment_start
+    }
+
+    fn add_trivia(&mut self, kind: TriviaKind, start: usize) {
+        let enabled = match kind {
+            TriviaKind::Whitespace => self.options.collect_whitespace,
+            _ => self.options.collect_comments,
+        };
+
+        if enabled {
+            let location = ItemLocation {
+                file: self.file.clone(),
+                start,
+                end: self.index,
+            };
+
+            self.trivia.add(Trivia { kind, location });
+        }
+    }
+
+    pub fn take_trivia(&mut self) -> TriviaList {
+        std::mem::take(&mut self.trivia)
+    }
+
+    fn eat_block_comment(&mut self, start: &str, end: &str, nested: bool) {
+        let comment_start = self.index;
+        let mut depth = 1;
+
+        self.index += start.len();
+
+        while depth > 0 {
+            if self.is_finished() {
+                self.set_lexical_error(ParseErrorKind::UnterminatedComment, comment_start);
+                return;
+            }
+
+            if nested && self.as_str().starts_with(start) {
+                self.index += start.len();
+                depth += 1;
+            } else if self.as_str().starts_with(end) {
+                self.index += end.len();
+                depth -= 1;
+            } else {
+                self.index += self.as_char().len_utf8();
             }
         }
     }
 
     pub fn read_function<F : Fn(&str) -> usize>(&mut self, f: F) -> Option<&str> {
+        if self.is_dedent_pending() {
+            return None;
+        }
+
         self.advance(f(self.as_str()))
     }
 
     pub fn read_string(&mut self, string: &str) -> Option<&str> {
+        if self.is_dedent_pending() {
+            return None;
+        }
+
         let length = match self.as_str().starts_with(string) {
             true => string.len(),
             false => return None
@@ -184,7 +695,11 @@ impl StringReader {
     }
 
     pub fn read_regex(&mut self, pattern: &'static str) -> Option<&str> {
-        let regex = get_regex(pattern);
+        if self.is_dedent_pending() {
+            return None;
+        }
+
+        let regex = self.get_regex(pattern)?;
         let length = match regex.find(self.as_str()) {
             Some(m) => m.end(),
             None => 0
@@ -194,9 +709,221 @@ impl StringReader {
     }
 
     pub fn peek_regex(&mut self, pattern: &'static str) -> bool {
-        let regex = get_regex(pattern);
+        match self.get_regex(pattern) {
+            Some(regex) => regex.find(self.as_str()).is_some(),
+            None => false
+        }
+    }
+
+    fn get_regex(&mut self, pattern: &'static str) -> Option<&'static Regex> {
+        let regex = *self.regexes.entry(pattern).or_insert_with(|| get_regex(pattern));
+
+        if regex.is_none() {
+            self.set_lexical_error(ParseErrorKind::InvalidRegex(pattern), self.index);
+        }
+
+        regex
+    }
+
+    // All items are parsed through this method, which detects left recursion and uses the memoization table if the `memoize` option is enabled
+    pub fn parse<T : Parsable + 'static>(&mut self) -> Option<T> {
+        let rule = TypeId::of::<T>();
+        let start = self.index;
+        let state = self.get_parse_state();
+
+        if let Some(frame_index) = self.find_frame(rule, start, &state) {
+            return self.recall_seed::<T>(frame_index);
+        }
+
+        if self.memo_table.is_some() {
+            if let Some(value) = self.recall_memoized::<T>(rule, start, &state) {
+                return value;
+            }
+
+            self.expected_traces.push(ExpectedTrace::default());
+        }
+
+        let errors_len = self.errors.len();
+
+        self.frame_count += 1;
+        self.frames.push(ParseFrame::new(self.frame_count, rule, start, state.clone()));
+
+        let value = match T::parse_item(self) {
+            Some(seed) if self.frames.last().unwrap().left_recursive => Some(self.grow_seed(start, seed)),
+            value => value
+        };
+        let frame = self.frames.pop().unwrap();
+
+        if value.is_none() {
+            self.discard_lexical_error(frame.id, start);
+        }
+
+        if self.memo_table.is_some() {
+            let expected = self.expected_traces.pop().unwrap();
+
+            if let Some(trace) = self.expected_traces.last_mut() {
+                trace.merge(&expected);
+            }
+
+            // Results that depend on the seed of a left recursion are only valid while it is grown.
+            // A successful parse may also leave markers modified, which a cache hit would not reproduce.
+            // Items that cannot be cloned are not cached, they are parsed again.
+            let cached_value = match &value {
+                _ if frame.involved => None,
+                Some(_) if self.get_parse_state() != state => None,
+                Some(value) => value.clone_item().map(|value| Some(Box::new(value) as Box<dyn Any>)),
+                None => Some(None)
+            };
+
+            if let Some(cached_value) = cached_value {
+                let entry = MemoEntry {
+                    value: cached_value,
+                    end: self.index,
+                    expected,
+                    errors: self.errors[errors_len.min(self.errors.len())..].to_vec(),
+                    lexical_error: self.lexical_error.as_ref()
+                        .filter(|lexical_error| lexical_error.frame_id >= frame.id)
+                        .map(|lexical_error| lexical_error.error.clone()),
+                    trivia: self.trivia.get_from(start)
+                };
+
+                self.memo_table().insert(rule, start, state, entry);
+            }
+        }
+
+        value
+    }
+
+    pub fn parse_with_separator<T : Parsable>(&mut self, separator: &'static str) -> Option<T> {
+        T::parse_item_with_separator(self, separator)
+    }
+
+    pub fn parse_without_consuming_spaces<T : Parsable>(&mut self) -> Option<T> {
+        T::parse_item_without_consuming_spaces(self)
+    }
+
+    pub fn parse_with_recovery_tokens<T : Parsable>(&mut self, separator: Option<&'static str>, tokens: RecoveryTokens) -> Option<T> {
+        T::parse_item_with_recovery_tokens(self, separator, tokens)
+    }
+
+    fn memo_table(&mut self) -> &mut MemoTable {
+        self.memo_table.as_mut().unwrap()
+    }
+
+    fn get_parse_state(&mut self) -> ParseState {
+        ParseState {
+            markers: self.markers.get_state(),
+            indentation_depth: self.indentation.as_ref().map(|stack| stack.depth()).unwrap_or(0)
+        }
+    }
+
+    // The frames of the items being parsed at the current index are at the top of the stack
+    fn find_frame(&self, rule: TypeId, index: usize, state: &ParseState) -> Option<usize> {
+        for (frame_index, frame) in self.frames.iter().enumerate().rev() {
+            if frame.index != index {
+                break;
+            }
+
+            if frame.rule == rule && frame.state == *state {
+                return Some(frame_index);
+            }
+        }
+
+        None
+    }
+
+    // The items parsed between the two calls are involved in the left recursion, and their result depends on the seed
+    fn recall_seed<T : Parsable + 'static>(&mut self, frame_index: usize) -> Option<T> {
+        self.frames[frame_index].left_recursive = true;
+
+        for frame in &mut self.frames[frame_index + 1..] {
+            frame.involved = true;
+        }
+
+        let (seed, end) = self.frames[frame_index].seed.as_ref()?;
+        let value = seed.downcast_ref::<T>().unwrap().clone_item()?;
+
+        self.index = *end;
+
+        Some(value)
+    }
+
+    fn recall_memoized<T : Parsable + 'static>(&mut self, rule: TypeId, start: usize, state: &ParseState) -> Option<Option<T>> {
+        let entry = self.memo_table().get(rule, start, state)?;
+        let value = match &entry.value {
+            Some(value) => Some(value.downcast_ref::<T>().unwrap().clone_item()?),
+            None => None
+        };
+        let end = entry.end;
+        let expected = entry.expected.clone();
+        let errors = entry.errors.clone();
+        let lexical_error = entry.lexical_error.clone();
+        let trivia = entry.trivia.clone();
+
+        self.index = end;
+        self.replay_expected(&expected);
+        self.errors.extend(errors);
+
+        for trivia in trivia {
+            self.trivia.add(trivia);
+        }
+
+        if let Some(error) = lexical_error {
+            self.keep_lexical_error(error, start);
+        }
 
-        regex.find(self.as_str()).is_some()
+        Some(value)
+    }
+
+    fn grow_seed<T : Parsable + 'static>(&mut self, start: usize, mut value: T) -> T {
+        loop {
+            let end = self.index;
+            let state = self.save_state();
+
+            self.frames.last_mut().unwrap().seed = Some((Box::new(value), end));
+            self.index = start;
+
+            let result = T::parse_item(self);
+            let seed = self.frames.last_mut().unwrap().seed.take().unwrap().0;
+
+            match result {
+                Some(result) if self.index > end => value = result,
+                _ => {
+                    self.restore_state(state);
+                    self.index = end;
+
+                    return *seed.downcast::<T>().unwrap();
+                }
+            }
+        }
+    }
+
+    fn save_state(&self) -> ReaderState {
+        ReaderState {
+            errors: self.errors.clone(),
+            lexical_error: self.lexical_error.clone(),
+            indentation: self.indentation.clone(),
+            trivia: self.trivia.clone()
+        }
+    }
+
+    fn restore_state(&mut self, state: ReaderState) {
+        self.errors = state.errors;
+        self.lexical_error = state.lexical_error;
+        self.indentation = state.indentation;
+        self.trivia = state.trivia;
+    }
+
+    fn replay_expected(&mut self, expected: &ExpectedTrace) {
+        for item in &expected.completion_items {
+            self.add_completion_item(|| item.clone());
+        }
+
+        if let Some(index) = expected.index {
+            for item in &expected.items {
+                self.set_expected_entity_at(index, item.clone());
+            }
+        }
     }
 
     pub fn get_item_location(&self, start: usize) -> ItemLocation {
@@ -236,11 +963,8 @@ impl StringReader {
     }
 }
 
-fn is_space(c: char) -> bool {
-    match c {
-        ' ' | '\r' | '\n' | '\t' => true,
-        _ => false
-    }
+fn is_line_break(c: char) -> bool {
+    matches!(c, '\r' | '\n')
 }
 
 fn is_inline_space(c: char) -> bool {
@@ -251,15 +975,9 @@ fn is_inline_space(c: char) -> bool {
 }
 
 fn is_string_alphanum(string: &str) -> bool {
-    for byte in string.as_bytes() {
-        if !is_alphanum(*byte as char) {
-            return false;
-        }
-    }
-
-    true
+    string.chars().all(is_alphanum)
 }
 
 fn is_alphanum(c: char) -> bool {
-    (c >= '0' && c <= '9') || (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || c == '_'
+    c.is_xid_continue()
 }
\ No newline at end of file
@@ -0,0 +1,36 @@
+// Literals and fields of an item in the order they are parsed, generated by `#[parsable]` from the attributes
+// of the item. It is implemented for the writers that can write all the fields: `SourceWriter` if they implement
+// `ToSource`, and `DocWriter` if they implement `Format`.
+pub trait Syntax<W : SyntaxWriter> {
+    fn write_syntax(&self, writer: &mut W);
+}
+
+pub trait SyntaxWriter : Sized {
+    // Prefix, suffix or string of a variant
+    fn literal(&mut self, literal: &'static str);
+
+    fn item<T : SyntaxItem<Self>>(&mut self, item: &T) {
+        item.write_item(self);
+    }
+
+    fn separated<T : SyntaxItem<Self>>(&mut self, items: &[T], separator: &'static str) {
+        T::write_separated(items, separator, self);
+    }
+
+    // Used to skip the prefix and suffix of a missing optional field
+    fn is_empty<T : SyntaxItem<Self>>(&self, item: &T) -> bool {
+        item.is_empty_item()
+    }
+}
+
+// Field that a writer can write: the items that implement `ToSource` for `SourceWriter`, and the ones that
+// implement `Format` for `DocWriter`. The failed items of `Recoverable<T>` fields are written as they were in the source.
+pub trait SyntaxItem<W> : Sized {
+    fn write_item(&self, writer: &mut W);
+
+    fn write_separated(items: &[Self], separator: &'static str, writer: &mut W);
+
+    // Missing items (`None`, an empty `Vec` or an empty `String`), whose prefixes and suffixes are skipped.
+    // Given by `ToSource::is_empty_source()` and `Format::is_empty_format()`.
+    fn is_empty_item(&self) -> bool;
+}
@@ -0,0 +1,319 @@
+use std::{rc::Rc, sync::Arc};
+use unicode_xid::UnicodeXID;
+use crate::{Parsable, SyntaxWriter, SyntaxItem, DocComment, Newline, Indent, Dedent, Include, Expression, ExpressionOperator, Recover, Recoverable};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Spacing {
+    // A space is only inserted between two tokens that would otherwise be read as one
+    #[default]
+    Minimal,
+    // A space is inserted between all tokens of a line
+    Always
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceOptions {
+    pub spacing: Spacing,
+    pub indentation: &'static str,
+    pub doc_comment_start: Option<&'static str>
+}
+
+impl Default for SourceOptions {
+    fn default() -> Self {
+        Self {
+            spacing: Spacing::default(),
+            indentation: "    ",
+            doc_comment_start: None
+        }
+    }
+}
+
+// Line breaks are written lazily, so that the indentation of the following line is known when it is written
+pub struct SourceWriter {
+    options: SourceOptions,
+    result: String,
+    indentation_level: usize,
+    pending_line_break: bool
+}
+
+impl SourceWriter {
+    pub fn new(options: SourceOptions) -> Self {
+        Self {
+            options,
+            result: String::new(),
+            indentation_level: 0,
+            pending_line_break: false
+        }
+    }
+
+    pub fn get_options(&self) -> &SourceOptions {
+        &self.options
+    }
+
+    pub fn token(&mut self, token: &str) -> &mut Self {
+        if token.is_empty() {
+            return self;
+        }
+
+        if self.pending_line_break {
+            self.result.push('\n');
+            self.result.push_str(&self.options.indentation.repeat(self.indentation_level));
+            self.pending_line_break = false;
+        } else if self.needs_space(token) {
+            self.result.push(' ');
+        }
+
+        self.result.push_str(token);
+        self
+    }
+
+    pub fn write<T : ToSource>(&mut self, item: &T) -> &mut Self {
+        item.write_source(self);
+        self
+    }
+
+    pub fn write_separated<T : ToSource>(&mut self, items: &[T], separator: &str) -> &mut Self {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.token(separator);
+            }
+
+            item.write_source(self);
+        }
+
+        self
+    }
+
+    pub fn line_break(&mut self) -> &mut Self {
+        if !self.result.is_empty() {
+            self.pending_line_break = true;
+        }
+
+        self
+    }
+
+    pub fn indent(&mut self) -> &mut Self {
+        self.indentation_level += 1;
+        self.line_break()
+    }
+
+    pub fn dedent(&mut self) -> &mut Self {
+        self.indentation_level = self.indentation_level.saturating_sub(1);
+        self.line_break()
+    }
+
+    pub fn finish(mut self) -> String {
+        if self.pending_line_break {
+            self.result.push('\n');
+        }
+
+        self.result
+    }
+
+    fn needs_space(&self, token: &str) -> bool {
+        let (Some(last), Some(first)) = (self.result.chars().last(), token.chars().next()) else {
+            return false;
+        };
+
+        if last == '\n' {
+            return false;
+        }
+
+        match self.options.spacing {
+            Spacing::Always => true,
+            Spacing::Minimal => are_joined(last, first)
+        }
+    }
+}
+
+impl SyntaxWriter for SourceWriter {
+    fn literal(&mut self, literal: &'static str) {
+        self.token(literal);
+    }
+}
+
+impl<T : ToSource> SyntaxItem<SourceWriter> for T {
+    fn write_item(&self, writer: &mut SourceWriter) {
+        writer.write(self);
+    }
+
+    fn write_separated(items: &[Self], separator: &'static str, writer: &mut SourceWriter) {
+        writer.write_separated(items, separator);
+    }
+
+    fn is_empty_item(&self) -> bool {
+        self.is_empty_source()
+    }
+}
+
+// Whether two adjacent tokens would be read as one if no space was inserted between them
+pub(crate) fn are_joined(last: char, first: char) -> bool {
+    (last.is_xid_continue() && first.is_xid_continue()) || (is_symbol(last) && is_symbol(first))
+}
+
+// Characters that may be part of the same operator as their neighbours
+fn is_symbol(c: char) -> bool {
+    !c.is_xid_continue() && !c.is_whitespace() && !matches!(c, '(' | ')' | '[' | ']' | '{' | '}' | ',' | ';')
+}
+
+pub trait ToSource {
+    fn write_source(&self, writer: &mut SourceWriter);
+
+    fn is_empty_source(&self) -> bool {
+        false
+    }
+
+    fn to_source(&self) -> String {
+        self.to_source_with_options(SourceOptions::default())
+    }
+
+    fn to_source_with_options(&self, options: SourceOptions) -> String {
+        let mut writer = SourceWriter::new(options);
+
+        self.write_source(&mut writer);
+        writer.finish()
+    }
+}
+
+impl ToSource for String {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        writer.token(self);
+    }
+
+    fn is_empty_source(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl ToSource for () {
+    fn write_source(&self, _writer: &mut SourceWriter) {
+
+    }
+
+    fn is_empty_source(&self) -> bool {
+        true
+    }
+}
+
+impl<T : ToSource> ToSource for Box<T> {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        self.as_ref().write_source(writer);
+    }
+
+    fn is_empty_source(&self) -> bool {
+        self.as_ref().is_empty_source()
+    }
+}
+
+impl<T : ToSource> ToSource for Rc<T> {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        self.as_ref().write_source(writer);
+    }
+
+    fn is_empty_source(&self) -> bool {
+        self.as_ref().is_empty_source()
+    }
+}
+
+impl<T : ToSource> ToSource for Arc<T> {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        self.as_ref().write_source(writer);
+    }
+
+    fn is_empty_source(&self) -> bool {
+        self.as_ref().is_empty_source()
+    }
+}
+
+impl<T : ToSource> ToSource for Option<T> {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        if let Some(item) = self {
+            item.write_source(writer);
+        }
+    }
+
+    fn is_empty_source(&self) -> bool {
+        self.is_none()
+    }
+}
+
+impl<T : ToSource> ToSource for Vec<T> {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        for item in self {
+            item.write_source(writer);
+        }
+    }
+
+    fn is_empty_source(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T : ToSource, U : ToSource> ToSource for (T, U) {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        self.0.write_source(writer);
+        self.1.write_source(writer);
+    }
+}
+
+// Doc comments are only written if the `doc_comment_start` option is set
+impl ToSource for DocComment {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        if let (Some(start), false) = (writer.get_options().doc_comment_start, self.is_empty()) {
+            writer.line_break();
+
+            for line in self.lines() {
+                writer.token(format!("{} {}", start, line).trim_end()).line_break();
+            }
+        }
+    }
+
+    fn is_empty_source(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl ToSource for Newline {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        writer.line_break();
+    }
+}
+
+impl ToSource for Indent {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        writer.indent();
+    }
+}
+
+impl ToSource for Dedent {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        writer.dedent();
+    }
+}
+
+// The included file is not written, only the path
+impl<T : Parsable> ToSource for Include<T> {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        writer.token(&format!("\"{}\"", self.path));
+    }
+}
+
+impl<T : Recover + ToSource> ToSource for Recoverable<T> {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        match self {
+            Self::Item(item) => item.write_source(writer),
+            Self::Error(location) => { writer.token(location.as_str().trim()); },
+        }
+    }
+}
+
+impl<T : Parsable + ToSource, O : ExpressionOperator + ToSource> ToSource for Expression<T, O> {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        match self {
+            Self::Operand(operand) => writer.write(operand),
+            Self::Prefix { operator, operand, .. } => writer.write(operator).write(operand.as_ref()),
+            Self::Postfix { operand, operator, .. } => writer.write(operand.as_ref()).write(operator),
+            Self::Binary { left, operator, right, .. } => writer.write(left.as_ref()).write(operator).write(right.as_ref()),
+        };
+    }
+}
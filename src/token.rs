@@ -27,8 +27,44 @@ macro_rules! create_token_struct {
             fn location(&self) -> &parsable::ItemLocation {
                 &self.location
             }
+
+            fn clone_item(&self) -> Option<Self> {
+                Some(Self {
+                    token: self.token,
+                    location: self.location.clone()
+                })
+            }
+        }
+
+        impl parsable::Node for $struct_name {
+            fn get_node_name(&self) -> String {
+                <Self as parsable::Parsable>::get_item_name()
+            }
+
+            fn get_node_location(&self) -> Option<&parsable::ItemLocation> {
+                Some(&self.location)
+            }
+        }
+
+        impl parsable::ToSource for $struct_name {
+            fn write_source(&self, writer: &mut parsable::SourceWriter) {
+                writer.token(self.token);
+            }
+        }
+
+        impl parsable::Format for $struct_name {
+            fn format(&self, _formatter: &mut parsable::Formatter) -> parsable::Doc {
+                parsable::Doc::text(self.token)
+            }
+
+            fn get_format_location(&self) -> Option<&parsable::ItemLocation> {
+                Some(&self.location)
+            }
         }
 
+        impl parsable::Visit for $struct_name {}
+        impl parsable::VisitMut for $struct_name {}
+
         impl std::ops::Deref for $struct_name {
             type Target = parsable::ItemLocation;
 
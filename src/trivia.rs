@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use crate::ItemLocation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    LineComment,
+    BlockComment,
+    Whitespace
+}
+
+#[derive(Debug, Clone)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub location: ItemLocation
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct TriviaList {
+    items: BTreeMap<usize, Trivia>
+}
+
+impl Trivia {
+    pub fn is_comment(&self) -> bool {
+        self.kind != TriviaKind::Whitespace
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.location.as_str()
+    }
+}
+
+impl TriviaList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, trivia: Trivia) {
+        self.items.insert(trivia.location.start, trivia);
+    }
+
+    // Trivia collected from the index belongs to items that are being discarded when the reader backtracks
+    pub(crate) fn truncate(&mut self, index: usize) {
+        self.items.split_off(&index);
+    }
+
+    pub(crate) fn get_from(&self, index: usize) -> Vec<Trivia> {
+        self.items.range(index..).map(|(_, trivia)| trivia.clone()).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Trivia> {
+        self.items.values()
+    }
+
+    pub fn comments(&self) -> impl Iterator<Item = &Trivia> {
+        self.iter().filter(|trivia| trivia.is_comment())
+    }
+
+    pub fn get_in(&self, location: &ItemLocation) -> impl Iterator<Item = &Trivia> {
+        let end = location.end;
+
+        self.items.range(location.start..end).map(|(_, trivia)| trivia).filter(move |trivia| trivia.location.end <= end)
+    }
+
+    // Comments directly preceding the location, each starting its own line, without blank lines in-between
+    pub fn get_leading(&self, location: &ItemLocation) -> Vec<&Trivia> {
+        let content = location.file.content.as_str();
+        let mut result = vec![];
+        let mut end = location.start;
+
+        for trivia in self.items.range(..location.start).rev().map(|(_, trivia)| trivia) {
+            if trivia.location.end > end {
+                break;
+            }
+
+            let gap = &content[trivia.location.end..end];
+
+            if !gap.trim().is_empty() || gap.matches('\n').count() > 1 {
+                break;
+            }
+
+            if trivia.is_comment() {
+                let line_start = content[..trivia.location.start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+                if !content[line_start..trivia.location.start].trim().is_empty() {
+                    break;
+                }
+
+                result.insert(0, trivia);
+            } else if trivia.as_str().matches('\n').count() > 1 {
+                break;
+            }
+
+            end = trivia.location.start;
+        }
+
+        result
+    }
+
+    // Comments directly following the location on the same line
+    pub fn get_trailing(&self, location: &ItemLocation) -> Vec<&Trivia> {
+        let content = location.file.content.as_str();
+        let mut result = vec![];
+        let mut start = location.end;
+
+        for trivia in self.items.range(location.end..).map(|(_, trivia)| trivia) {
+            let gap = &content[start..trivia.location.start];
+
+            if !gap.chars().all(|c| c == ' ' || c == '\t') {
+                break;
+            }
+
+            if trivia.is_comment() {
+                result.push(trivia);
+            } else if trivia.as_str().contains('\n') {
+                break;
+            }
+
+            start = trivia.location.end;
+        }
+
+        result
+    }
+}
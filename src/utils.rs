@@ -1,4 +1,35 @@
+use std::path::{Component, Path, PathBuf};
+
 #[allow(unused)]
 pub fn get_type_name<T>() -> String {
     std::any::type_name::<T>().split("::").last().unwrap().to_string()
-}
\ No newline at end of file
+}
+
+// Removes the `.` and `..` components of `path`, and makes it relative to `root_path` if it is inside it
+pub fn normalize_path(path: &Path, root_path: &Path) -> String {
+    let path = remove_dot_components(path);
+    let root_path = remove_dot_components(root_path);
+
+    match root_path.as_os_str().is_empty() {
+        true => path.to_string_lossy().to_string(),
+        false => path.strip_prefix(&root_path).unwrap_or(&path).to_string_lossy().to_string()
+    }
+}
+
+pub fn remove_dot_components(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {},
+            Component::ParentDir => match result.components().next_back() {
+                Some(Component::Normal(_)) => { result.pop(); },
+                Some(Component::RootDir) => {},
+                _ => result.push(component)
+            },
+            _ => result.push(component)
+        }
+    }
+
+    result
+}
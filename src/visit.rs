@@ -0,0 +1,391 @@
+use std::{any::{Any, TypeId}, collections::HashMap, rc::Rc, sync::Arc};
+use crate::{Parsable, DocComment, Newline, Indent, Dedent, Include, Expression, ExpressionOperator, Recover, Recoverable};
+
+type EnterHook<'a> = Box<dyn FnMut(&dyn Any) -> bool + 'a>;
+type ExitHook<'a> = Box<dyn FnMut(&dyn Any) + 'a>;
+type EnterHookMut<'a> = Box<dyn FnMut(&mut dyn Any) -> bool + 'a>;
+type ExitHookMut<'a> = Box<dyn FnMut(&mut dyn Any) + 'a>;
+
+// Hooks called on the items of a tree, registered for each type of item. Several hooks can be registered for the same
+// type, they are called in the order of registration. If an `enter` hook returns `false`, the children of the item are skipped.
+#[derive(Default)]
+pub struct Visitor<'a> {
+    enter_hooks: HashMap<TypeId, Vec<EnterHook<'a>>>,
+    exit_hooks: HashMap<TypeId, Vec<ExitHook<'a>>>
+}
+
+#[derive(Default)]
+pub struct VisitorMut<'a> {
+    enter_hooks: HashMap<TypeId, Vec<EnterHookMut<'a>>>,
+    exit_hooks: HashMap<TypeId, Vec<ExitHookMut<'a>>>
+}
+
+impl<'a> Visitor<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_enter<T : Any>(&mut self, mut hook: impl FnMut(&T) -> bool + 'a) -> &mut Self {
+        self.enter_hooks.entry(TypeId::of::<T>()).or_default().push(Box::new(move |item| hook(item.downcast_ref::<T>().unwrap())));
+        self
+    }
+
+    pub fn on_exit<T : Any>(&mut self, mut hook: impl FnMut(&T) + 'a) -> &mut Self {
+        self.exit_hooks.entry(TypeId::of::<T>()).or_default().push(Box::new(move |item| hook(item.downcast_ref::<T>().unwrap())));
+        self
+    }
+
+    // All the hooks are called, even if one of them returns `false`
+    pub fn enter<T : Any>(&mut self, item: &T) -> bool {
+        let mut visit_children = true;
+
+        for hook in self.enter_hooks.get_mut(&TypeId::of::<T>()).into_iter().flatten() {
+            visit_children &= hook(item);
+        }
+
+        visit_children
+    }
+
+    pub fn exit<T : Any>(&mut self, item: &T) {
+        for hook in self.exit_hooks.get_mut(&TypeId::of::<T>()).into_iter().flatten() {
+            hook(item);
+        }
+    }
+}
+
+impl<'a> VisitorMut<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_enter<T : Any>(&mut self, mut hook: impl FnMut(&mut T) -> bool + 'a) -> &mut Self {
+        self.enter_hooks.entry(TypeId::of::<T>()).or_default().push(Box::new(move |item| hook(item.downcast_mut::<T>().unwrap())));
+        self
+    }
+
+    pub fn on_exit<T : Any>(&mut self, mut hook: impl FnMut(&mut T) + 'a) -> &mut Self {
+        self.exit_hooks.entry(TypeId::of::<T>()).or_default().push(Box::new(move |item| hook(item.downcast_mut::<T>().unwrap())));
+        self
+    }
+
+    pub fn enter<T : Any>(&mut self, item: &mut T) -> bool {
+        let mut visit_children = true;
+
+        for hook in self.enter_hooks.get_mut(&TypeId::of::<T>()).into_iter().flatten() {
+            visit_children &= hook(item);
+        }
+
+        visit_children
+    }
+
+    pub fn exit<T : Any>(&mut self, item: &mut T) {
+        for hook in self.exit_hooks.get_mut(&TypeId::of::<T>()).into_iter().flatten() {
+            hook(item);
+        }
+    }
+}
+
+// Containers (`Vec`, `Option`, `Box`...) are transparent: only their content is passed to the visitor.
+// Implemented by `#[parsable]` for the generated types.
+pub trait Visit : Any {
+    #[allow(unused_variables)]
+    fn visit_children(&self, visitor: &mut Visitor) {
+
+    }
+
+    fn visit(&self, visitor: &mut Visitor) where Self : Sized {
+        if visitor.enter(self) {
+            self.visit_children(visitor);
+        }
+
+        visitor.exit(self);
+    }
+}
+
+pub trait VisitMut : Any {
+    #[allow(unused_variables)]
+    fn visit_children_mut(&mut self, visitor: &mut VisitorMut) {
+
+    }
+
+    fn visit_mut(&mut self, visitor: &mut VisitorMut) where Self : Sized {
+        if visitor.enter(self) {
+            self.visit_children_mut(visitor);
+        }
+
+        visitor.exit(self);
+    }
+}
+
+// Fields of a `#[parsable]` type, as seen by the generated `Visit` and `VisitMut` implementations. Fields whose type
+// does not implement the trait (e.g. a type that implements `Parsable` manually) are not passed to the visitor.
+#[doc(hidden)]
+pub struct VisitField<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub struct VisitFieldMut<'a, T>(pub &'a mut T);
+
+#[doc(hidden)]
+pub trait VisitFieldImpl {
+    fn visit_field(&self, visitor: &mut Visitor);
+}
+
+#[doc(hidden)]
+pub trait VisitFieldFallback {
+    fn visit_field(&self, visitor: &mut Visitor);
+}
+
+#[doc(hidden)]
+pub trait VisitFieldMutImpl {
+    fn visit_field_mut(&mut self, visitor: &mut VisitorMut);
+}
+
+#[doc(hidden)]
+pub trait VisitFieldMutFallback {
+    fn visit_field_mut(&mut self, visitor: &mut VisitorMut);
+}
+
+impl<T : Visit> VisitFieldImpl for VisitField<'_, T> {
+    fn visit_field(&self, visitor: &mut Visitor) {
+        self.0.visit(visitor);
+    }
+}
+
+// Only picked when `T` does not implement `Visit`, since the method takes one more reference
+impl<T> VisitFieldFallback for &VisitField<'_, T> {
+    fn visit_field(&self, _visitor: &mut Visitor) {
+
+    }
+}
+
+impl<T : VisitMut> VisitFieldMutImpl for VisitFieldMut<'_, T> {
+    fn visit_field_mut(&mut self, visitor: &mut VisitorMut) {
+        self.0.visit_mut(visitor);
+    }
+}
+
+impl<T> VisitFieldMutFallback for &mut VisitFieldMut<'_, T> {
+    fn visit_field_mut(&mut self, _visitor: &mut VisitorMut) {
+
+    }
+}
+
+// Strings are passed to the visitor like the other items
+impl Visit for String {}
+impl VisitMut for String {}
+
+impl Visit for () {
+    fn visit(&self, _visitor: &mut Visitor) {
+
+    }
+}
+
+impl VisitMut for () {
+    fn visit_mut(&mut self, _visitor: &mut VisitorMut) {
+
+    }
+}
+
+impl<T : Visit> Visit for Box<T> {
+    fn visit(&self, visitor: &mut Visitor) {
+        self.as_ref().visit(visitor);
+    }
+}
+
+impl<T : VisitMut> VisitMut for Box<T> {
+    fn visit_mut(&mut self, visitor: &mut VisitorMut) {
+        self.as_mut().visit_mut(visitor);
+    }
+}
+
+impl<T : Visit> Visit for Rc<T> {
+    fn visit(&self, visitor: &mut Visitor) {
+        self.as_ref().visit(visitor);
+    }
+}
+
+// Warning: items shared through `Rc` or `Arc` cannot be modified, so they are skipped (along with their children)
+// if they are also referenced elsewhere, e.g. by another part of the tree or by the memoization table of a parse
+// still in progress. Their `VisitorMut` hooks are not called.
+impl<T : VisitMut> VisitMut for Rc<T> {
+    fn visit_mut(&mut self, visitor: &mut VisitorMut) {
+        if let Some(item) = Rc::get_mut(self) {
+            item.visit_mut(visitor);
+        }
+    }
+}
+
+impl<T : Visit> Visit for Arc<T> {
+    fn visit(&self, visitor: &mut Visitor) {
+        self.as_ref().visit(visitor);
+    }
+}
+
+// Same as `Rc`: shared items are skipped
+impl<T : VisitMut> VisitMut for Arc<T> {
+    fn visit_mut(&mut self, visitor: &mut VisitorMut) {
+        if let Some(item) = Arc::get_mut(self) {
+            item.visit_mut(visitor);
+        }
+    }
+}
+
+impl<T : Visit> Visit for Option<T> {
+    fn visit(&self, visitor: &mut Visitor) {
+        if let Some(item) = self {
+            item.visit(visitor);
+        }
+    }
+}
+
+impl<T : VisitMut> VisitMut for Option<T> {
+    fn visit_mut(&mut self, visitor: &mut VisitorMut) {
+        if let Some(item) = self {
+            item.visit_mut(visitor);
+        }
+    }
+}
+
+impl<T : Visit> Visit for Vec<T> {
+    fn visit(&self, visitor: &mut Visitor) {
+        for item in self {
+            item.visit(visitor);
+        }
+    }
+}
+
+impl<T : VisitMut> VisitMut for Vec<T> {
+    fn visit_mut(&mut self, visitor: &mut VisitorMut) {
+        for item in self {
+            item.visit_mut(visitor);
+        }
+    }
+}
+
+impl<T : Visit, U : Visit> Visit for (T, U) {
+    fn visit(&self, visitor: &mut Visitor) {
+        self.0.visit(visitor);
+        self.1.visit(visitor);
+    }
+}
+
+impl<T : VisitMut, U : VisitMut> VisitMut for (T, U) {
+    fn visit_mut(&mut self, visitor: &mut VisitorMut) {
+        self.0.visit_mut(visitor);
+        self.1.visit_mut(visitor);
+    }
+}
+
+impl Visit for DocComment {}
+impl VisitMut for DocComment {}
+impl Visit for Newline {}
+impl VisitMut for Newline {}
+impl Visit for Indent {}
+impl VisitMut for Indent {}
+impl Visit for Dedent {}
+impl VisitMut for Dedent {}
+
+impl<T : Parsable + Visit> Visit for Include<T> {
+    fn visit_children(&self, visitor: &mut Visitor) {
+        self.item.visit(visitor);
+    }
+}
+
+impl<T : Parsable + VisitMut> VisitMut for Include<T> {
+    fn visit_children_mut(&mut self, visitor: &mut VisitorMut) {
+        self.item.visit_mut(visitor);
+    }
+}
+
+impl<T : Recover + Visit> Visit for Recoverable<T> {
+    fn visit(&self, visitor: &mut Visitor) {
+        match self {
+            Self::Item(item) => item.visit(visitor),
+            Self::Error(_) => {
+                visitor.enter(self);
+                visitor.exit(self);
+            }
+        }
+    }
+}
+
+impl<T : Recover + VisitMut> VisitMut for Recoverable<T> {
+    fn visit_mut(&mut self, visitor: &mut VisitorMut) {
+        match self {
+            Self::Item(item) => item.visit_mut(visitor),
+            Self::Error(_) => {
+                visitor.enter(self);
+                visitor.exit(self);
+            }
+        }
+    }
+}
+
+// Operands are passed to the visitor directly, other expressions are passed as `Expression<T, O>`
+impl<T : Parsable + Visit, O : ExpressionOperator + Visit> Visit for Expression<T, O> {
+    fn visit_children(&self, visitor: &mut Visitor) {
+        match self {
+            Self::Operand(operand) => operand.visit(visitor),
+            Self::Prefix { operator, operand, .. } => {
+                operator.visit(visitor);
+                operand.visit(visitor);
+            },
+            Self::Postfix { operand, operator, .. } => {
+                operand.visit(visitor);
+                operator.visit(visitor);
+            },
+            Self::Binary { left, operator, right, .. } => {
+                left.visit(visitor);
+                operator.visit(visitor);
+                right.visit(visitor);
+            },
+        }
+    }
+
+    fn visit(&self, visitor: &mut Visitor) {
+        match self {
+            Self::Operand(_) => self.visit_children(visitor),
+            _ => {
+                if visitor.enter(self) {
+                    self.visit_children(visitor);
+                }
+
+                visitor.exit(self);
+            }
+        }
+    }
+}
+
+impl<T : Parsable + VisitMut, O : ExpressionOperator + VisitMut> VisitMut for Expression<T, O> {
+    fn visit_children_mut(&mut self, visitor: &mut VisitorMut) {
+        match self {
+            Self::Operand(operand) => operand.visit_mut(visitor),
+            Self::Prefix { operator, operand, .. } => {
+                operator.visit_mut(visitor);
+                operand.visit_mut(visitor);
+            },
+            Self::Postfix { operand, operator, .. } => {
+                operand.visit_mut(visitor);
+                operator.visit_mut(visitor);
+            },
+            Self::Binary { left, operator, right, .. } => {
+                left.visit_mut(visitor);
+                operator.visit_mut(visitor);
+                right.visit_mut(visitor);
+            },
+        }
+    }
+
+    fn visit_mut(&mut self, visitor: &mut VisitorMut) {
+        match self {
+            Self::Operand(_) => self.visit_children_mut(visitor),
+            _ => {
+                if visitor.enter(self) {
+                    self.visit_children_mut(visitor);
+                }
+
+                visitor.exit(self);
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+
+use std::{fs, ops::Deref, path::{Path, PathBuf}};
+use parsable::*;
+
+#[parsable]
+pub struct Entries {
+    pub entries: Vec<Entry>
+}
+
+#[parsable]
+pub struct Entry {
+    #[parsable(regex=r"\w+", suffix=";")]
+    pub name: String
+}
+
+// Runs the test with memoization disabled, then enabled: both must give the same result
+pub fn with_and_without_memoization(options: ParseOptions, test: impl Fn(ParseOptions)) {
+    for memoize in [false, true] {
+        test(ParseOptions { memoize, ..options.clone() });
+    }
+}
+
+// Temporary directory containing the files of a test, removed when the test ends
+pub struct TestDirectory {
+    path: PathBuf
+}
+
+impl TestDirectory {
+    pub fn new<C : AsRef<[u8]>>(name: &str, files: &[(&str, C)]) -> Self {
+        let path = std::env::temp_dir().join(format!("parsable-{}-{}", name, std::process::id()));
+
+        fs::create_dir_all(&path).unwrap();
+
+        for (file_path, content) in files {
+            let file_path = path.join(file_path);
+
+            fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            fs::write(file_path, content).unwrap();
+        }
+
+        Self { path }
+    }
+
+    // The directory is the root of the package of the parsed files
+    pub fn options(&self) -> ParseOptions {
+        ParseOptions {
+            package_root_path: Some(self.path.to_string_lossy().to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+impl Deref for TestDirectory {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TestDirectory {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
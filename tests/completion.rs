@@ -0,0 +1,84 @@
+#![allow(dead_code)]
+
+mod common;
+
+use parsable::*;
+use common::with_and_without_memoization;
+
+#[parsable]
+enum Keyword {
+    Let = "let",
+    Loop = "loop",
+    While = "while"
+}
+
+#[parsable]
+struct Name {
+    #[parsable(regex=r"[a-z]+")]
+    name: String
+}
+
+#[parsable]
+enum Statement {
+    #[parsable(prefix="let", suffix=";")]
+    Let(Name),
+    #[parsable(suffix=";")]
+    Keyword(Keyword)
+}
+
+#[parsable]
+struct Declaration {
+    keyword: Option<Keyword>,
+    name: Name
+}
+
+#[parsable]
+struct Program {
+    statements: Vec<Statement>
+}
+
+fn labels(completion: &Completion) -> Vec<&str> {
+    completion.items.iter().map(|item| item.label.as_str()).collect()
+}
+
+#[test]
+fn word_at_the_cursor_filters_the_candidates() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let completion = Program::complete_at("let a; l".to_string(), 8, options);
+
+        assert_eq!(labels(&completion), vec!["let", "loop"]);
+        assert_eq!((completion.location.start, completion.location.end), (7, 8));
+    });
+}
+
+#[test]
+fn all_candidates_are_returned_after_whitespace() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let completion = Program::complete_at("let a; ".to_string(), 7, options);
+
+        assert_eq!(labels(&completion), vec!["let", "loop", "while"]);
+        assert_eq!((completion.location.start, completion.location.end), (7, 7));
+    });
+}
+
+#[test]
+fn labels_expected_several_times_are_returned_once() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let completion = Declaration::complete_at("l".to_string(), 1, options);
+        let item = &completion.items[0];
+
+        assert_eq!(labels(&completion), vec!["let", "loop"]);
+        assert_eq!(item.kind, CompletionKind::Suggestion);
+        assert_eq!(item.item_name, Some(Keyword::get_item_name()));
+    });
+}
+
+#[test]
+fn content_after_the_cursor_is_ignored() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let completion = Program::complete_at("wh x".to_string(), 2, options);
+
+        assert_eq!(labels(&completion), vec!["while"]);
+        assert_eq!((completion.location.start, completion.location.end), (0, 2));
+    });
+}
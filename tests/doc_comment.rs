@@ -0,0 +1,93 @@
+#![allow(dead_code)]
+
+mod common;
+
+use parsable::*;
+use common::with_and_without_memoization;
+
+#[parsable]
+struct Function {
+    doc: DocComment,
+    #[parsable(prefix="fn", regex=r"\w+", suffix=";")]
+    name: String
+}
+
+#[parsable]
+struct Program {
+    functions: Vec<Function>
+}
+
+fn options(doc_comment_start: Option<&'static str>) -> ParseOptions {
+    ParseOptions {
+        comment_start: Some("//"),
+        comments: vec![CommentSyntax::Block("/*", "*/")],
+        doc_comment_start,
+        ..Default::default()
+    }
+}
+
+// Parses with and without memoization, and checks that both give the same doc comments
+fn parse(content: &str, doc_comment_start: Option<&'static str>) -> Program {
+    let program = Program::parse(content.to_string(), options(doc_comment_start)).unwrap();
+
+    with_and_without_memoization(options(doc_comment_start), |options| {
+        let other = Program::parse(content.to_string(), options).unwrap();
+
+        for (function, other_function) in program.functions.iter().zip(&other.functions) {
+            assert_eq!(function.doc.content, other_function.doc.content);
+            assert_eq!((function.doc.location.start, function.doc.location.end), (other_function.doc.location.start, other_function.doc.location.end));
+        }
+    });
+
+    program
+}
+
+#[test]
+fn doc_comments_are_captured_without_their_delimiters() {
+    let program = parse("/// Does nothing.\n///   Really.\nfn foo;", Some("///"));
+    let doc = &program.functions[0].doc;
+
+    assert_eq!(doc.content, "Does nothing.\n  Really.");
+    assert_eq!(doc.lines().collect::<Vec<_>>(), ["Does nothing.", "  Really."]);
+    assert_eq!((doc.location.start, doc.location.end), (0, 31));
+}
+
+#[test]
+fn missing_doc_comments_are_empty() {
+    let program = parse("fn foo;\n\n/// Bar.\nfn bar;", Some("///"));
+    let doc = &program.functions[0].doc;
+
+    assert!(doc.is_empty());
+    assert_eq!(doc.content, "");
+    assert_eq!(doc.location.start, 0);
+    assert_eq!(program.functions[1].doc.content, "Bar.");
+}
+
+#[test]
+fn blank_lines_end_the_block() {
+    let program = parse("/// License.\n\n/// Foo.\nfn foo;", Some("///"));
+
+    assert_eq!(program.functions[0].doc.content, "Foo.");
+}
+
+#[test]
+fn regular_comments_are_not_doc_comments() {
+    let program = parse("/// Foo.\n// Not documentation.\nfn foo;\n/// Bar.\nfn bar; // trailing\n", Some("///"));
+
+    assert!(program.functions[0].doc.is_empty());
+    assert_eq!(program.functions[1].doc.content, "Bar.");
+}
+
+#[test]
+fn comments_on_the_same_line_as_other_tokens_are_not_doc_comments() {
+    let program = parse("fn foo; /* Foo. */\nfn bar;", None);
+
+    assert!(program.functions[1].doc.is_empty());
+}
+
+#[test]
+fn all_comments_are_doc_comments_without_a_doc_comment_start() {
+    let program = parse("/* Block\n   comment */\n// Line.\nfn foo;", None);
+
+    assert_eq!(program.functions[0].doc.content, "Block\n  comment\nLine.");
+}
@@ -0,0 +1,122 @@
+#![allow(dead_code)]
+
+use parsable::*;
+
+#[parsable]
+struct Number {
+    #[parsable(regex=r"\d+")]
+    value: String
+}
+
+#[parsable]
+enum Value {
+    Number(Number),
+    True = "true",
+    False = "false"
+}
+
+#[parsable]
+struct Assignment {
+    #[parsable(regex=r"[a-z]+", suffix="=")]
+    name: String,
+    #[parsable(suffix=";")]
+    value: Value
+}
+
+#[parsable]
+struct Program {
+    assignments: Vec<Assignment>
+}
+
+fn parse_error(content: &str, file_path: Option<&str>) -> ParseError {
+    let options = ParseOptions {
+        file_path: file_path.map(|path| path.to_string()),
+        ..Default::default()
+    };
+
+    Program::parse(content.to_string(), options).unwrap_err()
+}
+
+#[test]
+fn caret_points_at_the_error_column() {
+    let error = parse_error("a = 1;\nb = ;\nc = 2;", None);
+
+    assert_eq!(ErrorRenderer::default().render(&error), [
+        "error: expected one of \"false\", \"true\", Value",
+        " --> 2:5",
+        "  |",
+        "2 | b = ;",
+        "  |     ^",
+    ].join("\n"));
+}
+
+#[test]
+fn position_starts_with_the_file_path() {
+    let error = parse_error("a = 1;\nb = ;", Some("config.txt"));
+    let rendered = ErrorRenderer::default().render(&error);
+
+    assert_eq!(rendered.lines().nth(1), Some(" --> config.txt:2:5"));
+    assert_eq!(error.to_string(), rendered);
+}
+
+#[test]
+fn context_lines_surround_the_error_line() {
+    let error = parse_error("a = 1;\nb = 2;\nc = ;\nd = 3;", None);
+    let renderer = ErrorRenderer { context_lines: 1, ..Default::default() };
+
+    assert_eq!(renderer.render(&error), [
+        "error: expected one of \"false\", \"true\", Value",
+        " --> 3:5",
+        "  |",
+        "2 | b = 2;",
+        "3 | c = ;",
+        "  |     ^",
+        "4 | d = 3;",
+    ].join("\n"));
+}
+
+#[test]
+fn context_lines_stop_at_the_file_boundaries() {
+    let error = parse_error("a = ;", None);
+    let renderer = ErrorRenderer { context_lines: 3, ..Default::default() };
+
+    assert_eq!(renderer.render(&error).lines().skip(3).collect::<Vec<_>>(), ["1 | a = ;", "  |     ^"]);
+}
+
+#[test]
+fn tabs_are_expanded_before_placing_the_caret() {
+    let error = parse_error("a =\t;", None);
+    let lines = |renderer: ErrorRenderer| renderer.render(&error).lines().skip(3).map(|line| line.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(lines(ErrorRenderer::default()), ["1 | a = ;", "  |     ^"]);
+    assert_eq!(lines(ErrorRenderer { tab_width: 8, ..Default::default() }), ["1 | a =     ;", "  |         ^"]);
+}
+
+#[test]
+fn color_can_be_enabled() {
+    let error = parse_error("a = ;", None);
+    let rendered = ErrorRenderer { color: true, ..Default::default() }.render(&error);
+
+    assert!(rendered.starts_with("\x1b[1;31merror\x1b[0m: \x1b[1m"));
+    assert!(rendered.ends_with("\x1b[1;31m^\x1b[0m"));
+    assert!(!ErrorRenderer::default().render(&error).contains('\x1b'));
+}
+
+#[test]
+fn expected_items_are_sorted_and_deduplicated() {
+    let error = ParseError {
+        kind: ParseErrorKind::UnexpectedInput,
+        file: SharedFileInfo::new(FileInfo::new("x".to_string(), String::new(), String::new())),
+        index: 0,
+        expected: vec![]
+    };
+    let message = |expected: &[&str]| {
+        let error = ParseError { expected: expected.iter().map(|item| item.to_string()).collect(), ..error.clone() };
+
+        ErrorRenderer::default().render(&error).lines().next().unwrap().to_string()
+    };
+
+    assert_eq!(message(&["b", "a", "b"]), "error: expected one of a, b");
+    assert_eq!(message(&["a", "a"]), "error: expected a");
+    assert_eq!(message(&[]), "error: unexpected input");
+}
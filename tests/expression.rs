@@ -0,0 +1,102 @@
+#![allow(dead_code)]
+
+use parsable::*;
+
+#[parsable]
+enum Value {
+    Number(Number),
+    NegativeInfinity = "-inf"
+}
+
+#[parsable]
+struct Number {
+    #[parsable(regex=r"\d+")]
+    value: String
+}
+
+#[parsable]
+enum Operator {
+    Minus = "-",
+    Not = "!",
+    Bang = "?",
+    Caret = "^",
+    Tilde = "~"
+}
+
+impl ExpressionOperator for Operator {
+    fn get_binary_precedence(&self) -> Option<(u32, Associativity)> {
+        match self {
+            Operator::Minus => Some((1, Associativity::Left)),
+            Operator::Bang => Some((u32::MAX, Associativity::Right)),
+            Operator::Caret => Some((u32::MAX, Associativity::Left)),
+            Operator::Tilde => Some((u32::MAX - 1, Associativity::Left)),
+            _ => None
+        }
+    }
+
+    fn get_prefix_precedence(&self) -> Option<u32> {
+        match self {
+            Operator::Minus => Some(2),
+            Operator::Not => Some(u32::MAX),
+            _ => None
+        }
+    }
+}
+
+type Expr = Expression<Value, Operator>;
+
+#[test]
+fn operand_is_retried_when_prefix_operand_fails() {
+    let expression = Expr::parse("-inf - -1".to_string(), ParseOptions::default()).unwrap();
+
+    match expression {
+        Expression::Binary { left, right, .. } => {
+            assert!(matches!(*left, Expression::Operand(Value::NegativeInfinity)));
+            assert!(matches!(*right, Expression::Prefix { operator: Operator::Minus, .. }));
+        },
+        _ => panic!("expected a binary expression")
+    }
+}
+
+#[test]
+fn maximum_precedence_does_not_overflow() {
+    let prefix = Expr::parse("!1".to_string(), ParseOptions::default()).unwrap();
+    let binary = Expr::parse("1 ? 2 ? 3".to_string(), ParseOptions::default()).unwrap();
+
+    assert!(matches!(prefix, Expression::Prefix { operator: Operator::Not, .. }));
+
+    match binary {
+        Expression::Binary { left, right, .. } => {
+            assert!(matches!(*left, Expression::Operand(_)));
+            assert!(matches!(*right, Expression::Binary { .. }));
+        },
+        _ => panic!("expected a binary expression")
+    }
+}
+
+#[test]
+fn maximum_precedence_keeps_left_associativity() {
+    let expression = Expr::parse("1 ^ 2 ^ 3".to_string(), ParseOptions::default()).unwrap();
+
+    match expression {
+        Expression::Binary { left, right, .. } => {
+            assert!(matches!(*left, Expression::Binary { operator: Operator::Caret, .. }));
+            assert!(matches!(*right, Expression::Operand(_)));
+        },
+        _ => panic!("expected a binary expression")
+    }
+}
+
+#[test]
+fn adjacent_maximum_precedences_are_distinct() {
+    let expression = Expr::parse("1 ~ 2 ^ 3".to_string(), ParseOptions::default()).unwrap();
+
+    match expression {
+        Expression::Binary { operator, left, right, .. } => {
+            assert!(matches!(operator, Operator::Tilde));
+            assert!(matches!(*left, Expression::Operand(_)));
+            assert!(matches!(*right, Expression::Binary { operator: Operator::Caret, .. }));
+        },
+        _ => panic!("expected a binary expression")
+    }
+}
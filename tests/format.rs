@@ -0,0 +1,173 @@
+#![allow(dead_code)]
+
+use parsable::*;
+
+#[parsable(impl_format=true)]
+struct Identifier {
+    #[parsable(regex=r"[a-z]+")]
+    name: String
+}
+
+#[parsable(impl_format=true)]
+struct Call {
+    name: Identifier,
+    #[parsable(brackets="()", separator=",")]
+    arguments: Vec<Identifier>
+}
+
+// Each statement is written on its own line
+#[parsable]
+struct Statement {
+    #[parsable(suffix=";")]
+    call: Call
+}
+
+impl Format for Statement {
+    fn format(&self, formatter: &mut Formatter) -> Doc {
+        Doc::concat(vec![formatter.format_syntax(self), Doc::HardLine])
+    }
+
+    fn get_format_location(&self) -> Option<&ItemLocation> {
+        Some(&self.location)
+    }
+}
+
+#[parsable(impl_format=true)]
+struct Program {
+    statements: Vec<Statement>
+}
+
+#[parsable(impl_format=true)]
+enum Block {
+    If(IfBlock),
+    Simple(Call, Newline)
+}
+
+#[parsable(impl_format=true)]
+struct IfBlock {
+    #[parsable(prefix="if", suffix=":")]
+    condition: Identifier,
+    indent: Indent,
+    #[parsable(min=1)]
+    body: Vec<Block>,
+    dedent: Dedent
+}
+
+#[parsable(impl_format=true)]
+struct IndentedProgram {
+    blocks: Vec<Block>
+}
+
+// Implements `Parsable` and `Format` manually, but not `ToSource`
+#[derive(Debug)]
+struct Ident {
+    name: String,
+    location: ItemLocation
+}
+
+impl Parsable for Ident {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let start = reader.get_index();
+        let name = reader.read_regex(r"[a-z]+")?.to_string();
+
+        Some(Self { name, location: reader.get_item_location(start) })
+    }
+
+    fn get_item_name() -> String {
+        "identifier".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+}
+
+impl Format for Ident {
+    fn format(&self, _formatter: &mut Formatter) -> Doc {
+        Doc::text(&self.name)
+    }
+
+    fn get_format_location(&self) -> Option<&ItemLocation> {
+        Some(&self.location)
+    }
+}
+
+#[parsable(impl_format=true)]
+struct Tuple {
+    #[parsable(brackets="()", separator=",")]
+    items: Vec<Ident>
+}
+
+#[parsable(impl_format=true)]
+struct Digits {
+    #[parsable(prefix="\"", regex=r"\d+", suffix="\"")]
+    value: String
+}
+
+#[parsable(impl_format=true)]
+struct StringLiteral {
+    #[parsable(regex=r#""[^"]*""#)]
+    value: String
+}
+
+// `Digits` eats the spaces and comments after its opening quote before failing
+#[parsable(impl_format=true)]
+enum Literal {
+    Digits(Digits),
+    String(StringLiteral)
+}
+
+fn format<T : Parsable + Format + 'static>(content: &str, width: usize) -> String {
+    let parse_options = ParseOptions {
+        comment_start: Some("//"),
+        comments: vec![CommentSyntax::Block("/*", "*/")],
+        ..Default::default()
+    };
+    let options = FormatOptions { width, ..Default::default() };
+
+    T::format_source(content.to_string(), parse_options, options).unwrap()
+}
+
+#[test]
+fn groups_are_written_on_one_line_if_they_fit() {
+    let content = "f( aaaa ,bbbb );";
+
+    assert_eq!(format::<Program>(content, 14), "f(aaaa, bbbb);\n");
+    assert_eq!(format::<Program>(content, 13), "f(\n    aaaa,\n    bbbb\n);\n");
+}
+
+#[test]
+fn comments_are_reattached_to_their_items() {
+    let content = "// first\nf(a); // trailing\n\n// before g\ng(b, /* inside */ c); h();\n// last\n";
+
+    assert_eq!(format::<Program>(content, 80), "// first\nf(a); // trailing\n\n// before g\ng(b, /* inside */ c);\nh();\n// last\n");
+}
+
+#[test]
+fn line_comments_break_their_group() {
+    let content = "f(a, // first\nb);";
+
+    assert_eq!(format::<Program>(content, 80), "f(\n    a, // first\n    b\n);\n");
+}
+
+#[test]
+fn indent_and_dedent_change_the_indentation() {
+    let content = "if a:\n  f(b)\n  if c:\n     g(d)\nh(e)\n";
+    let parse_options = ParseOptions { indentation_sensitive: true, ..Default::default() };
+    let formatted = IndentedProgram::format_source(content.to_string(), parse_options.clone(), FormatOptions::default()).unwrap();
+
+    assert_eq!(formatted, "if a:\n    f(b)\n    if c:\n        g(d)\nh(e)\n");
+    assert!(IndentedProgram::parse(formatted, parse_options).is_ok());
+}
+
+#[test]
+fn items_without_to_source_can_be_formatted() {
+    assert_eq!(format::<Tuple>("( a ,b )", 80), "(a, b)\n");
+    assert_eq!(format::<Tuple>("(aaaa, bbbb)", 8), "(\n    aaaa,\n    bbbb\n)\n");
+}
+
+#[test]
+fn comment_delimiters_inside_tokens_are_not_comments() {
+    assert_eq!(format::<Literal>(r#"" /* c */ x""#, 80), "\" /* c */ x\"\n");
+    assert_eq!(format::<Literal>(r#"" /* c */ 1""#, 80), "\"1\" /* c */\n");
+}
@@ -0,0 +1,75 @@
+#![allow(dead_code)]
+
+mod common;
+
+use std::fs;
+use parsable::*;
+use common::{Entry, TestDirectory};
+
+#[parsable]
+struct ConfigFile {
+    statements: Vec<Statement>
+}
+
+#[parsable]
+enum Statement {
+    #[parsable(prefix="include")]
+    Include(Box<Include<ConfigFile>>),
+    Entry(Entry)
+}
+
+fn parse(directory: &TestDirectory, path: &str) -> Result<ConfigFile, ParseError> {
+    ConfigFile::parse_file(directory.join(path), directory.options())
+}
+
+#[test]
+fn included_files_are_parsed() {
+    let directory = TestDirectory::new("include-nested", &[("a.cfg", "x; include \"b.cfg\""), ("b.cfg", "y; z;")]);
+    let config = parse(&directory, "a.cfg").unwrap();
+
+    let Statement::Include(include) = &config.statements[1] else { panic!("expected an include") };
+
+    assert_eq!(include.path, "b.cfg");
+    assert_eq!(include.item.statements.len(), 2);
+    assert_eq!(include.item.statements[1].location().file.path, "b.cfg");
+}
+
+#[test]
+fn a_file_that_includes_itself_is_a_cycle() {
+    let directory = TestDirectory::new("include-direct", &[("a.cfg", "x; include \"a.cfg\"")]);
+    let error = parse(&directory, "a.cfg").unwrap_err();
+
+    assert_eq!(error.kind, ParseErrorKind::IncludeCycle("a.cfg".to_string()));
+    assert_eq!(error.file.path, "a.cfg");
+    assert_eq!(error.index, 11);
+}
+
+#[test]
+fn indirect_cycles_name_the_file_already_being_parsed() {
+    let directory = TestDirectory::new("include-indirect", &[("a.cfg", "include \"b.cfg\""), ("b.cfg", "y;\ninclude \"a.cfg\"")]);
+    let error = parse(&directory, "a.cfg").unwrap_err();
+
+    assert_eq!(error.kind, ParseErrorKind::IncludeCycle("a.cfg".to_string()));
+    assert_eq!(error.file.path, "b.cfg");
+    assert_eq!(error.index, 11);
+
+    let rendered = ErrorRenderer::default().render(&error);
+
+    assert!(rendered.contains("include cycle: a.cfg is already being parsed"), "{}", rendered);
+    assert!(rendered.contains("b.cfg:2:9"), "{}", rendered);
+}
+
+#[test]
+fn included_files_are_in_the_package_of_the_including_file() {
+    let directory = TestDirectory::new("include-package", &[("a.cfg", "x; include \"b.cfg\""), ("b.cfg", "y;")]);
+    let package_root_path = directory.to_string_lossy().to_string();
+    let mut source_map = SourceMap::new();
+    let id = source_map.add_file(fs::read_to_string(directory.join("a.cfg")).unwrap(), "a.cfg".to_string(), package_root_path.clone());
+    let config = source_map.parse::<ConfigFile>(id, ParseOptions::default()).unwrap().unwrap();
+
+    let Statement::Include(include) = &config.statements[1] else { panic!("expected an include") };
+    let file = &include.item.statements[0].location().file;
+
+    assert_eq!(file.path, "b.cfg");
+    assert_eq!(file.package_root_path, package_root_path);
+}
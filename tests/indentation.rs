@@ -0,0 +1,106 @@
+#![allow(dead_code)]
+
+mod common;
+
+use parsable::*;
+use common::with_and_without_memoization;
+
+#[parsable]
+struct Word {
+    #[parsable(regex=r"[a-z]+")]
+    name: String
+}
+
+#[parsable]
+enum Statement {
+    If(IfStatement),
+    Simple(Word, Newline)
+}
+
+#[parsable]
+struct IfStatement {
+    #[parsable(prefix="if", suffix=":")]
+    condition: Word,
+    indent: Indent,
+    #[parsable(min=1)]
+    body: Vec<Statement>,
+    dedent: Dedent
+}
+
+#[parsable]
+struct Program {
+    statements: Vec<Statement>
+}
+
+#[parsable]
+struct Line {
+    word: Word,
+    newline: Newline
+}
+
+#[parsable]
+struct Lines {
+    lines: Vec<Line>,
+    blanks: Vec<Newline>
+}
+
+// The `else` branch starts with a `Dedent`, which must be undone when the branch fails
+#[parsable]
+struct When {
+    #[parsable(prefix="when", suffix=":")]
+    condition: Word,
+    indent: Indent,
+    #[parsable(min=1)]
+    body: Vec<Line>,
+    otherwise: Option<Otherwise>,
+    dedent: Option<Dedent>,
+    next: Option<Word>
+}
+
+#[parsable]
+struct Otherwise {
+    dedent: Dedent,
+    #[parsable(value="else")]
+    keyword: String
+}
+
+fn options() -> ParseOptions {
+    ParseOptions {
+        indentation_sensitive: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn dedent_is_kept_when_a_sibling_fails_at_its_index() {
+    with_and_without_memoization(options(), |options| {
+        let program = Program::parse("if a:\n  b\nc\n".to_string(), options.clone()).unwrap();
+        let nested = Program::parse("if a:\n  if b:\n    c\nd\n".to_string(), options).unwrap();
+
+        assert_eq!(program.statements.len(), 2);
+        assert_eq!(nested.statements.len(), 2);
+    });
+}
+
+#[test]
+fn backtracking_over_dedent_restores_the_level() {
+    with_and_without_memoization(options(), |options| {
+        let when = When::parse("when a:\n  b\nc".to_string(), options).unwrap();
+
+        assert!(when.otherwise.is_none());
+        assert!(when.dedent.is_some());
+        assert_eq!(when.next.unwrap().name, "c");
+    });
+}
+
+#[test]
+fn last_line_is_ended_by_the_end_of_the_input() {
+    with_and_without_memoization(options(), |options| {
+        let ended = Lines::parse("a\nb\n".to_string(), options.clone()).unwrap();
+        let unended = Lines::parse("a\nb".to_string(), options).unwrap();
+
+        assert_eq!(ended.lines.len(), 2);
+        assert!(ended.blanks.is_empty());
+        assert_eq!(unended.lines.len(), 2);
+    });
+}
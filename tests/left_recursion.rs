@@ -0,0 +1,127 @@
+#![allow(dead_code)]
+
+mod common;
+
+use parsable::*;
+use common::with_and_without_memoization;
+
+#[parsable]
+struct Number {
+    #[parsable(regex=r"[0-9]+")]
+    value: String
+}
+
+// Sum = Sum "+" Number | Number
+#[parsable]
+enum Sum {
+    Add(Box<Sum>, PlusToken, Number),
+    Single(Number)
+}
+
+create_token_struct!(PlusToken, "+");
+
+#[parsable]
+struct Statement {
+    sum: Sum,
+    #[parsable(value=";")]
+    semicolon: String
+}
+
+// Indirect left recursion: Callee = Call | Name, Call = Callee "()"
+#[parsable]
+enum Callee {
+    Call(Box<Call>),
+    Name(Number)
+}
+
+#[parsable]
+struct Call {
+    callee: Callee,
+    #[parsable(value="()")]
+    parentheses: String
+}
+
+#[parsable]
+struct Word {
+    #[parsable(regex=r"[a-z]+")]
+    name: String
+}
+
+#[parsable]
+struct Arguments {
+    name: Word,
+    #[parsable(brackets="()", separator=",")]
+    arguments: Vec<Word>
+}
+
+#[parsable]
+enum Line {
+    Semicolon(Arguments, SemicolonToken),
+    Dot(Arguments, DotToken)
+}
+
+create_token_struct!(SemicolonToken, ";");
+create_token_struct!(DotToken, ".");
+
+fn sum_to_string(sum: &Sum) -> String {
+    match sum {
+        Sum::Add(left, _, right) => format!("({} + {})", sum_to_string(left), right.value),
+        Sum::Single(number) => number.value.clone(),
+    }
+}
+
+fn callee_to_string(callee: &Callee) -> String {
+    match callee {
+        Callee::Call(call) => format!("{}()", callee_to_string(&call.callee)),
+        Callee::Name(number) => number.value.clone(),
+    }
+}
+
+#[test]
+fn root_item_is_left_recursive() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let sum = Sum::parse("1 + 2 + 3".to_string(), options).unwrap();
+
+        assert_eq!(sum_to_string(&sum), "((1 + 2) + 3)");
+    });
+}
+
+#[test]
+fn left_recursive_field_by_value() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let statement = Statement::parse("1 + 2 + 3 + 4;".to_string(), options).unwrap();
+
+        assert_eq!(sum_to_string(&statement.sum), "(((1 + 2) + 3) + 4)");
+        assert_eq!(statement.location().as_str(), "1 + 2 + 3 + 4;");
+    });
+}
+
+#[test]
+fn indirect_left_recursion() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let callee = Callee::parse("1()()".to_string(), options).unwrap();
+
+        assert_eq!(callee_to_string(&callee), "1()()");
+        assert!(matches!(&callee, Callee::Call(call) if matches!(&call.callee, Callee::Call(_))));
+    });
+}
+
+#[test]
+fn left_recursion_failure() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let error = Statement::parse("1 + 2 3;".to_string(), options.clone()).unwrap_err();
+
+        assert_eq!(error.index, 0);
+        assert!(Sum::parse("+ 1".to_string(), options).is_err());
+    });
+}
+
+#[test]
+fn memoized_failure_does_not_duplicate_expected_items() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let error = Line::parse("f(a b".to_string(), options).unwrap_err();
+
+        assert_eq!(error.index, 4);
+        assert_eq!(error.expected, vec!["\",\"".to_string(), "\")\"".to_string()]);
+    });
+}
@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+
+mod common;
+
+use parsable::*;
+use common::with_and_without_memoization;
+
+// A quoted string is a file path only if the file can be read
+#[parsable]
+enum Import {
+    File(Include<Word>),
+    Name(Name)
+}
+
+#[parsable]
+struct Name {
+    #[parsable(regex=r#""[^"]*""#)]
+    value: String
+}
+
+#[parsable]
+struct Word {
+    #[parsable(regex=r"\w+")]
+    value: String
+}
+
+#[parsable]
+enum Pattern {
+    Broken(Broken),
+    Word(Word)
+}
+
+#[parsable]
+struct Broken {
+    #[parsable(regex=r"(")]
+    value: String
+}
+
+#[parsable]
+struct Sequence {
+    #[parsable(prefix="use")]
+    import: Import,
+    #[parsable(suffix=";")]
+    word: Word
+}
+
+#[test]
+fn error_in_failed_branch_is_discarded() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let import = Import::parse(r#""missing.txt""#.to_string(), options.clone()).unwrap();
+        let pattern = Pattern::parse("word".to_string(), options).unwrap();
+
+        assert!(matches!(import, Import::Name(name) if name.value == r#""missing.txt""#));
+        assert!(matches!(pattern, Pattern::Word(word) if word.value == "word"));
+    });
+}
+
+#[test]
+fn error_in_failed_branch_is_reported_if_parsing_fails_there() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let error = Pattern::parse("+".to_string(), options).unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidRegex("("));
+        assert_eq!(error.index, 0);
+    });
+}
+
+#[test]
+fn error_in_failed_branch_is_not_reported_if_parsing_fails_further() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let error = Sequence::parse(r#"use "missing.txt" word"#.to_string(), options).unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::UnexpectedInput);
+        assert_eq!(error.index, 22);
+    });
+}
@@ -0,0 +1,62 @@
+use parsable::*;
+
+fn file(content: &str) -> FileInfo {
+    FileInfo::new(content.to_string(), String::new(), String::new())
+}
+
+#[test]
+fn lines_start_after_each_line_break() {
+    let file = file("ab\ncd\n\nef");
+
+    assert_eq!(file.get_line_count(), 4);
+    assert_eq!(file.get_line_col(0), Some((1, 1)));
+    assert_eq!(file.get_line_col(2), Some((1, 3)));
+    assert_eq!(file.get_line_col(3), Some((2, 1)));
+    assert_eq!(file.get_line_col(6), Some((3, 1)));
+    assert_eq!(file.get_line_col(7), Some((4, 1)));
+    assert_eq!(file.get_line_col(8), Some((4, 2)));
+}
+
+#[test]
+fn end_of_the_content_has_a_position() {
+    assert_eq!(file("ab\ncd").get_line_col(5), Some((2, 3)));
+    assert_eq!(file("ab\n").get_line_col(3), Some((2, 1)));
+    assert_eq!(file("ab\n").get_line_col(4), None);
+}
+
+#[test]
+fn empty_content_has_one_line() {
+    let file = file("");
+
+    assert_eq!(file.get_line_count(), 1);
+    assert_eq!(file.get_line_col(0), Some((1, 1)));
+    assert_eq!(file.get_line_col(1), None);
+}
+
+#[test]
+fn carriage_returns_are_part_of_their_line() {
+    let file = file("a\r\nb");
+
+    assert_eq!(file.get_line_count(), 2);
+    assert_eq!(file.get_line_col(1), Some((1, 2)));
+    assert_eq!(file.get_line_col(3), Some((2, 1)));
+}
+
+#[test]
+fn item_locations_use_the_line_index_of_their_file() {
+    let file = SharedFileInfo::new(file("first\nsecond line"));
+    let location = ItemLocation { file, start: 13, end: 17 };
+
+    assert_eq!(location.get_start_line_col(), (2, 8));
+    assert_eq!(location.as_str(), "line");
+}
+
+#[test]
+fn large_contents_are_indexed() {
+    let content = "line\n".repeat(100_000);
+    let file = file(&content);
+
+    assert_eq!(file.get_line_count(), 100_001);
+    assert_eq!(file.get_line_col(5 * 54_321 + 2), Some((54_322, 3)));
+    assert_eq!(file.get_line_col(content.len()), Some((100_001, 1)));
+}
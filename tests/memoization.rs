@@ -0,0 +1,98 @@
+use std::cell::Cell;
+use parsable::*;
+
+thread_local! {
+    static PARSE_COUNT : Cell<usize> = const { Cell::new(0) };
+}
+
+// Neither `Clone` nor `clone_item()`: cannot be reused by the memoization table
+struct Word {
+    location: ItemLocation
+}
+
+impl Parsable for Word {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let start = reader.get_index();
+
+        PARSE_COUNT.with(|count| count.set(count.get() + 1));
+        reader.read_regex(r"[a-z]+")?;
+
+        Some(Self { location: reader.get_item_location(start) })
+    }
+
+    fn get_item_name() -> String {
+        "word".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+}
+
+struct ClonableWord {
+    location: ItemLocation
+}
+
+impl Parsable for ClonableWord {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        Word::parse_item(reader).map(|word| Self { location: word.location })
+    }
+
+    fn get_item_name() -> String {
+        "word".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+
+    fn clone_item(&self) -> Option<Self> {
+        Some(Self { location: self.location.clone() })
+    }
+}
+
+// Parses `T` twice at the same index, as a grammar that backtracks on a common prefix would
+struct Backtrack<T> {
+    item: T
+}
+
+impl<T : Parsable + 'static> Parsable for Backtrack<T> {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let start = reader.get_index();
+
+        reader.parse::<T>()?;
+        reader.set_index(start);
+
+        reader.parse::<T>().map(|item| Self { item })
+    }
+
+    fn get_item_name() -> String {
+        T::get_item_name()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        self.item.location()
+    }
+}
+
+fn count_parses<T : Parsable + 'static>(memoize: bool) -> (usize, usize) {
+    let options = ParseOptions { memoize, ..Default::default() };
+
+    PARSE_COUNT.with(|count| count.set(0));
+
+    let item = T::parse("abc".to_string(), options).unwrap();
+
+    (PARSE_COUNT.with(|count| count.get()), item.location().end)
+}
+
+#[test]
+fn clonable_items_are_reused() {
+    assert_eq!(count_parses::<Backtrack<ClonableWord>>(false), (2, 3));
+    assert_eq!(count_parses::<Backtrack<ClonableWord>>(true), (1, 3));
+}
+
+#[test]
+fn items_that_cannot_be_cloned_are_parsed_again() {
+    assert_eq!(count_parses::<Backtrack<Word>>(false), (2, 3));
+    assert_eq!(count_parses::<Backtrack<Word>>(true), (2, 3));
+}
@@ -0,0 +1,104 @@
+#![allow(dead_code)]
+
+use parsable::*;
+
+#[parsable]
+struct NumberLiteral {
+    #[parsable(regex=r"\d+")]
+    value: String
+}
+
+#[parsable]
+enum Operator {
+    Plus = "+",
+    Minus = "-"
+}
+
+#[parsable]
+enum Operand {
+    Number(NumberLiteral),
+    Negated(MinusToken, NumberLiteral)
+}
+
+#[parsable]
+struct Operation {
+    left: Operand,
+    operator: Operator,
+    right: Operand,
+    #[parsable(ignore)]
+    comment: String
+}
+
+create_token_struct!(MinusToken, "~");
+
+// Implements `Parsable` manually, and none of the other traits
+#[derive(Debug)]
+struct Ident {
+    name: String,
+    location: ItemLocation
+}
+
+impl Parsable for Ident {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let start = reader.get_index();
+        let name = reader.read_regex(r"[a-z]+")?.to_string();
+
+        Some(Self { name, location: reader.get_item_location(start) })
+    }
+
+    fn get_item_name() -> String {
+        "identifier".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+}
+
+// `Ident` implements neither `ToSource` nor `Format`, which are only implemented if enabled
+#[parsable]
+struct Assignment {
+    name: Ident,
+    #[parsable(prefix="=")]
+    value: Operand
+}
+
+#[test]
+fn children_are_generated_from_the_fields() {
+    let operation = Operation::parse("1 + ~2".to_string(), ParseOptions::default()).unwrap();
+    let names : Vec<String> = operation.get_children().iter().map(|child| child.get_node_name()).collect();
+
+    assert_eq!(operation.get_node_name(), "Operation");
+    assert_eq!(operation.get_node_location().unwrap().as_str(), "1 + ~2");
+    assert_eq!(names, ["Operand", "\"~\"", "NumberLiteral"]);
+    assert!(operation.get_children()[2].get_children().is_empty());
+}
+
+#[test]
+fn node_at_finds_the_innermost_item() {
+    let operation = Operation::parse("1 + ~2".to_string(), ParseOptions::default()).unwrap();
+    let chain : Vec<String> = operation.node_at(5).iter().map(|node| node.get_node_name()).collect();
+
+    assert_eq!(chain, ["Operation", "NumberLiteral"]);
+}
+
+#[test]
+fn enums_with_a_single_field_are_nodes() {
+    let operation = Operation::parse("1 + ~2".to_string(), ParseOptions::default()).unwrap();
+    let chain : Vec<String> = operation.node_at(0).iter().map(|node| node.get_node_name()).collect();
+    let operand = operation.get_children()[0];
+
+    assert_eq!(chain, ["Operation", "Operand", "NumberLiteral"]);
+    assert_eq!(operand.get_node_location().unwrap().as_str(), "1");
+    assert_eq!(operand.get_children()[0].get_node_name(), "NumberLiteral");
+}
+
+#[test]
+fn fields_that_are_not_nodes_are_part_of_the_text() {
+    let assignment = Assignment::parse("a = 1".to_string(), ParseOptions::default()).unwrap();
+    let names : Vec<String> = assignment.get_children().iter().map(|child| child.get_node_name()).collect();
+    let chain : Vec<String> = assignment.node_at(0).iter().map(|node| node.get_node_name()).collect();
+
+    assert_eq!(names, ["Operand"]);
+    assert_eq!(chain, ["Assignment"]);
+}
@@ -0,0 +1,80 @@
+#![allow(dead_code)]
+
+mod common;
+
+use std::io;
+use parsable::*;
+use common::{Entries, Entry, TestDirectory};
+
+#[test]
+fn paths_are_relative_to_the_package_root() {
+    let directory = TestDirectory::new("parse-file-paths", &[("sub/a.txt", b"a; b;")]);
+    let entries = Entries::parse_file(directory.join("sub/./../sub/a.txt"), directory.options()).unwrap();
+
+    assert_eq!(entries.entries[1].location.file.path, "sub/a.txt");
+    assert_eq!(entries.entries[1].location.file.package_root_path, directory.to_string_lossy());
+    assert_eq!(entries.entries[1].location.as_str(), "b;");
+}
+
+#[test]
+fn paths_outside_the_package_root_are_kept() {
+    let directory = TestDirectory::new("parse-file-outside", &[("a.txt", b"a;")]);
+    let options = ParseOptions {
+        package_root_path: Some(directory.join("sub").to_string_lossy().to_string()),
+        file_path: Some("ignored.txt".to_string()),
+        ..Default::default()
+    };
+    let entry = Entry::parse_file(directory.join("a.txt"), options).unwrap();
+
+    assert_eq!(entry.location.file.path, directory.join("a.txt").to_string_lossy());
+}
+
+#[test]
+fn byte_order_mark_is_skipped() {
+    let directory = TestDirectory::new("parse-file-bom", &[("a.txt", b"\xEF\xBB\xBFa; b;")]);
+    let entries = Entries::parse_file(directory.join("a.txt"), directory.options()).unwrap();
+
+    assert_eq!(entries.entries[0].location.file.content, "a; b;");
+    assert_eq!((entries.entries[0].location.start, entries.entries[0].location.end), (0, 2));
+    assert_eq!(entries.entries[0].name, "a");
+}
+
+#[test]
+fn missing_files_are_io_errors() {
+    let directory = TestDirectory::new::<&str>("parse-file-missing", &[]);
+    let error = Entries::parse_file(directory.join("missing.txt"), directory.options()).unwrap_err();
+
+    assert_eq!(error.kind, ParseErrorKind::Io(io::ErrorKind::NotFound));
+    assert_eq!(error.file.path, "missing.txt");
+    assert_eq!(error.index, 0);
+
+    let rendered = ErrorRenderer::default().render(&error);
+
+    assert!(rendered.contains("cannot read file"), "{}", rendered);
+    assert!(rendered.ends_with("--> missing.txt"), "{}", rendered);
+}
+
+#[test]
+fn invalid_utf8_points_at_the_first_invalid_byte() {
+    let directory = TestDirectory::new("parse-file-utf8", &[("a.txt", b"a;\nb\xFF;")]);
+    let error = Entries::parse_file(directory.join("a.txt"), directory.options()).unwrap_err();
+
+    assert_eq!(error.kind, ParseErrorKind::InvalidUtf8);
+    assert_eq!(error.file.path, "a.txt");
+    assert_eq!(error.index, 4);
+
+    let rendered = ErrorRenderer::default().render(&error);
+
+    assert!(rendered.contains("invalid UTF-8"), "{}", rendered);
+    assert!(rendered.contains("a.txt:2:2"), "{}", rendered);
+}
+
+#[test]
+fn syntax_errors_are_located_in_the_file() {
+    let directory = TestDirectory::new("parse-file-syntax", &[("a.txt", b"\xEF\xBB\xBFa;\nb c;")]);
+    let error = Entries::parse_file(directory.join("a.txt"), directory.options()).unwrap_err();
+
+    assert_eq!(error.kind, ParseErrorKind::UnexpectedInput);
+    assert_eq!(error.file.path, "a.txt");
+    assert_eq!(error.index, 5);
+}
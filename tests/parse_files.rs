@@ -0,0 +1,59 @@
+#![cfg(feature = "sync")]
+#![allow(dead_code)]
+
+mod common;
+
+use std::{io, path::PathBuf};
+use parsable::*;
+use common::{Entries, TestDirectory};
+
+#[test]
+fn results_are_in_input_order() {
+    let files : Vec<(String, String)> = (0..20).map(|i| (format!("{}.txt", i), "entry; ".repeat(i))).collect();
+    let files : Vec<(&str, &str)> = files.iter().map(|(path, content)| (path.as_str(), content.as_str())).collect();
+    let directory = TestDirectory::new("parse-files-order", &files);
+    let paths : Vec<PathBuf> = (0..20).map(|i| directory.join(format!("{}.txt", i))).collect();
+
+    let results = parse_files::<Entries, _>(&paths, directory.options());
+
+    assert_eq!(results.len(), 20);
+
+    for (i, result) in results.into_iter().enumerate() {
+        let entries = result.unwrap();
+
+        assert_eq!(entries.entries.len(), i);
+
+        if let Some(entry) = entries.entries.first() {
+            assert_eq!(entry.location.file.path, format!("{}.txt", i));
+            assert_eq!(entry.location.file.package_root_path, directory.to_string_lossy());
+        }
+    }
+}
+
+#[test]
+fn errors_are_reported_per_file() {
+    let directory = TestDirectory::new("parse-files-errors", &[("valid.txt", "a; b;"), ("invalid.txt", "a b;")]);
+    let paths = [directory.join("valid.txt"), directory.join("missing.txt"), directory.join("invalid.txt")];
+
+    let results = parse_files::<Entries, _>(&paths, directory.options());
+
+    assert_eq!(results[0].as_ref().unwrap().entries.len(), 2);
+
+    let missing = results[1].as_ref().unwrap_err();
+
+    assert_eq!(missing.kind, ParseErrorKind::Io(io::ErrorKind::NotFound));
+    assert_eq!(missing.file.path, "missing.txt");
+
+    let invalid = results[2].as_ref().unwrap_err();
+
+    assert_eq!(invalid.kind, ParseErrorKind::UnexpectedInput);
+    assert_eq!(invalid.file.path, "invalid.txt");
+    assert_eq!(invalid.index, 2);
+}
+
+#[test]
+fn no_files_give_no_results() {
+    let paths : [PathBuf; 0] = [];
+
+    assert!(parse_files::<Entries, _>(&paths, ParseOptions::default()).is_empty());
+}
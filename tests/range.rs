@@ -0,0 +1,73 @@
+#![allow(dead_code)]
+
+use parsable::*;
+
+#[parsable]
+struct Word {
+    #[parsable(regex=r"\S+")]
+    value: String
+}
+
+#[parsable]
+struct Sentence {
+    words: Vec<Word>
+}
+
+fn parse(content: &str) -> Sentence {
+    Sentence::parse(content.to_string(), ParseOptions::default()).unwrap()
+}
+
+#[test]
+fn ranges_start_at_line_and_character_zero() {
+    let sentence = parse("abc\n  def");
+
+    assert_eq!(sentence.words[0].location.to_range(ColumnUnit::Utf16), Range::new(Position::new(0, 0), Position::new(0, 3)));
+    assert_eq!(sentence.words[1].location.to_range(ColumnUnit::Utf16), Range::new(Position::new(1, 2), Position::new(1, 5)));
+}
+
+#[test]
+fn characters_are_counted_in_the_chosen_unit() {
+    let sentence = parse("🦀 été");
+    let location = &sentence.words[1].location;
+
+    assert_eq!(location.as_str(), "été");
+    assert_eq!(location.to_range(ColumnUnit::Byte), Range::new(Position::new(0, 5), Position::new(0, 10)));
+    assert_eq!(location.to_range(ColumnUnit::Utf16), Range::new(Position::new(0, 3), Position::new(0, 6)));
+    assert_eq!(location.to_range(ColumnUnit::Char), Range::new(Position::new(0, 2), Position::new(0, 5)));
+}
+
+#[test]
+fn locations_are_built_from_ranges() {
+    let sentence = parse("🦀 été\nfin");
+    let file = &sentence.location.file;
+
+    for unit in [ColumnUnit::Byte, ColumnUnit::Char, ColumnUnit::Utf16] {
+        for word in &sentence.words {
+            let location = ItemLocation::from_range(file, word.location.to_range(unit), unit).unwrap();
+
+            assert_eq!((location.start, location.end), (word.location.start, word.location.end));
+        }
+    }
+}
+
+#[test]
+fn invalid_ranges_are_rejected() {
+    let sentence = parse("abc\ndef");
+    let file = &sentence.location.file;
+    let range = |start: (usize, usize), end: (usize, usize)| Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1));
+
+    assert!(ItemLocation::from_range(file, range((1, 1), (0, 2)), ColumnUnit::Utf16).is_none());
+    assert!(ItemLocation::from_range(file, range((0, 0), (0, 4)), ColumnUnit::Utf16).is_none());
+    assert!(ItemLocation::from_range(file, range((0, 0), (2, 0)), ColumnUnit::Utf16).is_none());
+}
+
+#[test]
+fn ranges_contain_their_bounds() {
+    let range = Range::new(Position::new(1, 4), Position::new(2, 0));
+
+    assert!(range.contains(Position::new(1, 4)));
+    assert!(range.contains(Position::new(1, 100)));
+    assert!(range.contains(Position::new(2, 0)));
+    assert!(!range.contains(Position::new(1, 3)));
+    assert!(!range.contains(Position::new(2, 1)));
+}
@@ -0,0 +1,149 @@
+#![allow(dead_code)]
+
+mod common;
+
+use parsable::*;
+use common::with_and_without_memoization;
+
+#[parsable]
+struct Number {
+    #[parsable(regex=r"[0-9]+")]
+    value: String
+}
+
+#[parsable(recover_terminators=";", recover_delimiters=["}"])]
+struct Statement {
+    #[parsable(suffix=";")]
+    number: Number
+}
+
+// Implemented manually
+#[parsable]
+struct Entry {
+    #[parsable(regex=r"[a-z]+", suffix=",")]
+    name: String
+}
+
+impl Recover for Entry {
+    fn get_recovery_terminators() -> &'static [&'static str] {
+        &[","]
+    }
+
+    fn get_recovery_delimiters() -> &'static [&'static str] {
+        &["]"]
+    }
+}
+
+#[parsable]
+struct List {
+    #[parsable(brackets="[]")]
+    entries: Vec<Recoverable<Entry>>
+}
+
+#[parsable(recover_delimiters=[")", "]"])]
+enum Value {
+    Number(Number),
+    #[parsable(brackets="()")]
+    List(List)
+}
+
+#[parsable]
+struct Block {
+    #[parsable(brackets="{}")]
+    statements: Vec<Recoverable<Statement>>
+}
+
+// The block is parsed again at the same index when the first variant fails
+#[parsable]
+enum Item {
+    Exclamation(Block, ExclamationToken),
+    Question(Block, QuestionToken)
+}
+
+// The same item recovers differently in each list
+impl Recover for Number {}
+
+#[parsable]
+struct Arguments {
+    #[parsable(brackets="()", separator=",", recover_delimiters=[",", ")"])]
+    numbers: Vec<Recoverable<Number>>
+}
+
+#[parsable]
+struct Sequence {
+    #[parsable(brackets="{}", recover_terminators=";", recover_delimiters="}")]
+    numbers: Vec<Recoverable<Number>>
+}
+
+create_token_struct!(ExclamationToken, "!");
+create_token_struct!(QuestionToken, "?");
+
+#[test]
+fn recovered_errors() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let (block, errors) = Block::parse_with_recovery("{ 1; x; 2; }".to_string(), options);
+        let block = block.unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].index, 5);
+        assert_eq!(block.statements.iter().map(|statement| statement.is_error()).collect::<Vec<_>>(), vec![false, true, false]);
+    });
+}
+
+#[test]
+fn memoized_item_replays_its_errors() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let (item, errors) = Item::parse_with_recovery("{ 1; x; 2; }?".to_string(), options);
+
+        let Some(Item::Question(block, _)) = item else {
+            panic!("expected the second variant");
+        };
+
+        assert_eq!(errors.len(), 1);
+        assert!(block.statements[1].is_error());
+    });
+}
+
+#[test]
+fn synchronization_tokens_are_declared_by_attributes() {
+    assert_eq!(Statement::get_recovery_terminators(), [";"]);
+    assert_eq!(Statement::get_recovery_delimiters(), ["}"]);
+    assert!(Value::get_recovery_terminators().is_empty());
+    assert_eq!(Value::get_recovery_delimiters(), [")", "]"]);
+}
+
+#[test]
+fn manual_synchronization_tokens() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let (list, errors) = List::parse_with_recovery("[a, 1, b, 2 ]".to_string(), options);
+        let list = list.unwrap();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(list.entries.iter().map(|entry| entry.is_error()).collect::<Vec<_>>(), vec![false, true, false, true]);
+    });
+}
+
+#[test]
+fn synchronization_tokens_are_declared_by_fields() {
+    with_and_without_memoization(ParseOptions::default(), |options| {
+        let (arguments, errors) = Arguments::parse_with_recovery("(1, x y, 2)".to_string(), options.clone());
+        let numbers = arguments.unwrap().numbers;
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(numbers.iter().map(|number| number.is_error()).collect::<Vec<_>>(), vec![false, true, false]);
+        assert_eq!(numbers[1].location().as_str(), "x y");
+
+        let (sequence, errors) = Sequence::parse_with_recovery("{ 1 x, y; 2 }".to_string(), options);
+        let numbers = sequence.unwrap().numbers;
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(numbers.iter().map(|number| number.is_error()).collect::<Vec<_>>(), vec![false, true, false]);
+        assert_eq!(numbers[1].location().as_str(), "x, y;");
+    });
+}
+
+#[test]
+fn field_synchronization_tokens_are_ignored_without_recovery() {
+    assert!(Arguments::parse("(1, 2)".to_string(), ParseOptions::default()).is_ok());
+    assert!(Arguments::parse("(1, x, 2)".to_string(), ParseOptions::default()).is_err());
+}
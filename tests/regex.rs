@@ -0,0 +1,82 @@
+#![allow(dead_code)]
+
+use std::thread;
+use parsable::*;
+
+#[parsable]
+struct Word {
+    #[parsable(regex=r"[a-z]+")]
+    value: String
+}
+
+#[parsable]
+struct Number {
+    #[parsable(regex=r"\d+")]
+    value: String
+}
+
+#[parsable]
+enum Token {
+    Word(Word),
+    Number(Number)
+}
+
+#[parsable]
+struct Tokens {
+    tokens: Vec<Token>
+}
+
+#[parsable]
+struct Broken {
+    #[parsable(regex=r"[a-")]
+    value: String
+}
+
+#[test]
+fn invalid_regex_is_reported_with_its_pattern() {
+    let error = Broken::parse("abc".to_string(), ParseOptions::default()).unwrap_err();
+
+    assert_eq!(error.kind, ParseErrorKind::InvalidRegex("[a-"));
+    assert_eq!(error.index, 0);
+    assert!(ErrorRenderer::default().render(&error).contains("invalid regex /[a-/"));
+}
+
+#[test]
+fn invalid_regex_is_reported_each_time_it_is_used() {
+    for _ in 0..2 {
+        let error = Broken::parse("abc".to_string(), ParseOptions::default()).unwrap_err();
+
+        assert_eq!(error.kind, ParseErrorKind::InvalidRegex("[a-"));
+    }
+}
+
+#[test]
+fn invalid_regex_is_reported_with_recovery() {
+    let (result, errors) = Broken::parse_with_recovery("abc".to_string(), ParseOptions::default());
+
+    assert!(result.is_none());
+    assert!(errors.iter().any(|error| error.kind == ParseErrorKind::InvalidRegex("[a-")));
+}
+
+#[test]
+fn regexes_can_be_used_from_several_threads() {
+    let handles : Vec<_> = (0..8).map(|i| thread::spawn(move || {
+        let mut counts = vec![];
+
+        for j in 0..50 {
+            let content = format!("abc {} def {} {}", i, j, "x".repeat(j));
+            let tokens = Tokens::parse(content, ParseOptions::default()).unwrap();
+            let error = Broken::parse("abc".to_string(), ParseOptions::default()).unwrap_err();
+
+            assert_eq!(error.kind, ParseErrorKind::InvalidRegex("[a-"));
+
+            counts.push(tokens.tokens.iter().filter(|token| matches!(token, Token::Number(_))).count());
+        }
+
+        counts
+    })).collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), vec![2; 50]);
+    }
+}
@@ -0,0 +1,58 @@
+use parsable::*;
+
+fn file(content: &str) -> FileInfo {
+    FileInfo::new(content.to_string(), String::new(), String::new())
+}
+
+#[test]
+fn line_and_column_are_converted_to_an_index() {
+    let file = file("ab\ncd");
+
+    assert_eq!(file.get_index(1, 1), Some(0));
+    assert_eq!(file.get_index(1, 3), Some(2));
+    assert_eq!(file.get_index(2, 2), Some(4));
+    assert_eq!(file.get_index(2, 3), Some(5));
+}
+
+#[test]
+fn columns_can_be_expressed_in_each_unit() {
+    // "é" is 2 bytes and 1 UTF-16 code unit, "🦀" is 4 bytes and 2 UTF-16 code units
+    let file = file("é🦀x");
+
+    assert_eq!(file.get_index_with_unit(1, 3, ColumnUnit::Byte), Some(2));
+    assert_eq!(file.get_index_with_unit(1, 7, ColumnUnit::Byte), Some(6));
+    assert_eq!(file.get_index_with_unit(1, 3, ColumnUnit::Char), Some(6));
+    assert_eq!(file.get_index_with_unit(1, 4, ColumnUnit::Utf16), Some(6));
+    assert_eq!(file.get_index_with_unit(1, 5, ColumnUnit::Utf16), Some(7));
+}
+
+#[test]
+fn columns_inside_a_character_are_rejected() {
+    let file = file("é🦀x");
+
+    assert_eq!(file.get_index_with_unit(1, 2, ColumnUnit::Byte), None);
+    assert_eq!(file.get_index_with_unit(1, 3, ColumnUnit::Utf16), None);
+}
+
+#[test]
+fn positions_outside_of_the_content_are_rejected() {
+    let file = file("ab\ncd");
+
+    assert_eq!(file.get_index(0, 1), None);
+    assert_eq!(file.get_index(1, 0), None);
+    assert_eq!(file.get_index(1, 4), None);
+    assert_eq!(file.get_index(3, 1), None);
+}
+
+#[test]
+fn reverse_lookup_is_the_inverse_of_line_col() {
+    let file = file("fn é() {\n\t🦀 + x\r\n}\n");
+
+    for unit in [ColumnUnit::Byte, ColumnUnit::Char, ColumnUnit::Utf16] {
+        for (index, _) in file.content.char_indices() {
+            let (line, col) = file.get_line_col_with_unit(index, unit).unwrap();
+
+            assert_eq!(file.get_index_with_unit(line, col, unit), Some(index), "{:?} at {}", unit, index);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use parsable::*;
+
+#[parsable]
+struct Entries {
+    entries: Vec<Entry>
+}
+
+#[parsable]
+struct Entry {
+    #[parsable(regex=r"\w+", suffix=";")]
+    name: String
+}
+
+fn add_file(source_map: &mut SourceMap, path: &str, content: &str) -> FileId {
+    source_map.add_file(content.to_string(), path.to_string(), "src".to_string())
+}
+
+#[test]
+fn files_with_the_same_path_keep_their_id() {
+    let mut source_map = SourceMap::new();
+    let a = add_file(&mut source_map, "src/a.txt", "a;");
+    let b = add_file(&mut source_map, "src/b.txt", "b;");
+    let new_a = add_file(&mut source_map, "src/a.txt", "a; c;");
+
+    assert_ne!(a, b);
+    assert_eq!(a, new_a);
+    assert_eq!(source_map.len(), 2);
+    assert_eq!(source_map.get_file_id("src/b.txt"), Some(b));
+    assert_eq!(source_map.get_file_id("src/c.txt"), None);
+    assert_eq!(source_map.get_file(a).unwrap().content, "a; c;");
+    assert_eq!(source_map.get_file(a).unwrap().id, Some(a));
+}
+
+#[test]
+fn locations_from_successive_parses_are_equal() {
+    let mut source_map = SourceMap::new();
+    let id = add_file(&mut source_map, "src/a.txt", "a; b;");
+    let first = source_map.parse::<Entries>(id, ParseOptions::default()).unwrap().unwrap();
+
+    add_file(&mut source_map, "src/a.txt", "a; b; c;");
+
+    let second = source_map.parse::<Entries>(id, ParseOptions::default()).unwrap().unwrap();
+
+    assert_eq!(first.entries[1].location, second.entries[1].location);
+    assert_eq!(first.entries[1].location.get_hash(), second.entries[1].location.get_hash());
+    assert_ne!(first.entries[0].location, second.entries[1].location);
+    assert_eq!(second.entries.len(), 3);
+    assert_eq!(second.entries[2].location.file.path, "src/a.txt");
+
+    let locations : HashSet<ItemLocation> = first.entries.iter().chain(&second.entries).map(|entry| entry.location.clone()).collect();
+
+    assert_eq!(locations.len(), 3);
+}
+
+#[test]
+fn locations_in_different_files_are_not_equal() {
+    let mut source_map = SourceMap::new();
+    let a = add_file(&mut source_map, "src/a.txt", "a;");
+    let b = add_file(&mut source_map, "src/b.txt", "a;");
+    let in_a = source_map.parse::<Entry>(a, ParseOptions::default()).unwrap().unwrap();
+    let in_b = source_map.parse::<Entry>(b, ParseOptions::default()).unwrap().unwrap();
+
+    assert_ne!(in_a.location, in_b.location);
+    assert_ne!(in_a.location.get_hash(), in_b.location.get_hash());
+}
+
+#[test]
+fn options_do_not_change_the_file() {
+    let mut source_map = SourceMap::new();
+    let id = add_file(&mut source_map, "src/a.txt", "a;");
+    let options = ParseOptions {
+        file_path: Some("other.txt".to_string()),
+        ..Default::default()
+    };
+    let entry = source_map.parse::<Entry>(id, options).unwrap().unwrap();
+
+    assert_eq!(entry.location.file.path, "src/a.txt");
+    assert_eq!(entry.location.file.package_root_path, "src");
+}
+
+#[test]
+fn locations_can_be_built_from_ids() {
+    let mut source_map = SourceMap::new();
+    let id = add_file(&mut source_map, "src/a.txt", "a; b;");
+    let entries = source_map.parse::<Entries>(id, ParseOptions::default()).unwrap().unwrap();
+    let location = source_map.get_location(id, 3, 5).unwrap();
+
+    assert_eq!(location, entries.entries[1].location);
+    assert_eq!(location.as_str(), "b;");
+    assert!(source_map.get_location(id, 3, 6).is_none());
+    assert!(source_map.get_location(id, 4, 3).is_none());
+}
+
+#[test]
+fn unknown_ids_are_rejected() {
+    let source_map = SourceMap::new();
+
+    assert!(source_map.is_empty());
+    assert!(source_map.get_file(FileId(0)).is_none());
+    assert!(source_map.get_location(FileId(0), 0, 0).is_none());
+    assert!(source_map.parse::<Entry>(FileId(0), ParseOptions::default()).is_none());
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "sync")]
+#![allow(dead_code)]
+
+use std::sync::Arc;
+use parsable::*;
+
+#[parsable]
+struct Number {
+    #[parsable(regex=r"\d+")]
+    value: String
+}
+
+impl Recover for Number {
+    fn get_recovery_terminators() -> &'static [&'static str] {
+        &[";"]
+    }
+}
+
+#[parsable]
+enum Operator {
+    Plus = "+",
+    Minus = "-"
+}
+
+impl ExpressionOperator for Operator {
+    fn get_binary_precedence(&self) -> Option<(u32, Associativity)> {
+        Some((1, Associativity::Left))
+    }
+}
+
+#[parsable]
+struct Statement {
+    expression: Expression<Number, Operator>,
+    shared: Option<Arc<Number>>,
+    #[parsable(suffix=";")]
+    items: Vec<Recoverable<Number>>
+}
+
+const fn assert_send_sync<T : Send + Sync>() {}
+
+const _: () = {
+    assert_send_sync::<ItemLocation>();
+    assert_send_sync::<ParseError>();
+    assert_send_sync::<SharedFileInfo>();
+    assert_send_sync::<Statement>();
+    assert_send_sync::<Result<Statement, ParseError>>();
+};
+
+#[test]
+fn parsed_items_can_be_sent_to_other_threads() {
+    let statement = Statement::parse("1 + 2 3 4;".to_string(), ParseOptions::default()).unwrap();
+    let handle = std::thread::spawn(move || (statement.shared.unwrap().value.clone(), statement.items.len()));
+
+    assert_eq!(handle.join().unwrap(), ("3".to_string(), 1));
+}
@@ -0,0 +1,185 @@
+#![allow(dead_code)]
+
+use parsable::*;
+
+#[parsable(impl_to_source=true)]
+struct Identifier {
+    #[parsable(regex=r"[a-z]+")]
+    name: String
+}
+
+#[parsable(impl_to_source=true)]
+struct Number {
+    #[parsable(regex=r"\d+")]
+    value: String
+}
+
+#[parsable(impl_to_source=true)]
+struct Call {
+    name: Identifier,
+    #[parsable(brackets="()", separator=",")]
+    arguments: Vec<Value>,
+    #[parsable(prefix="->")]
+    return_type: Option<Identifier>
+}
+
+#[parsable(impl_to_source=true)]
+enum Value {
+    Number(Number),
+    Call(Call),
+    Nil = "nil",
+    Identifier(Identifier),
+    #[parsable(brackets="[]", separator=",")]
+    List(Vec<Value>),
+    #[parsable(brackets="()")]
+    Parenthesized(Box<Value>)
+}
+
+#[parsable(impl_to_source=true)]
+struct Let {
+    #[parsable(value="let")]
+    keyword: String,
+    #[parsable(value="mut", optional=true)]
+    mutable: String,
+    name: Identifier,
+    #[parsable(prefix="=", suffix=";")]
+    value: Value
+}
+
+#[parsable(impl_to_source=true)]
+struct Program {
+    statements: Vec<Let>
+}
+
+#[parsable(impl_to_source=true)]
+enum Operator {
+    Minus = "-"
+}
+
+#[parsable(impl_to_source=true)]
+struct Range {
+    bounds: (Number, Number)
+}
+
+// Implements `Parsable` and `ToSource` manually, but not `Format`
+#[derive(Debug)]
+struct Ident {
+    name: String,
+    location: ItemLocation
+}
+
+impl Parsable for Ident {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let start = reader.get_index();
+        let name = reader.read_regex(r"[a-z]+")?.to_string();
+
+        Some(Self { name, location: reader.get_item_location(start) })
+    }
+
+    fn get_item_name() -> String {
+        "identifier".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+}
+
+impl ToSource for Ident {
+    fn write_source(&self, writer: &mut SourceWriter) {
+        writer.token(&self.name);
+    }
+}
+
+#[parsable(impl_to_source=true)]
+struct Assignment {
+    name: Ident,
+    #[parsable(prefix="=", separator=",")]
+    values: Vec<Number>
+}
+
+#[parsable(impl_to_source=true)]
+struct Block {
+    #[parsable(brackets="{}", separator=";")]
+    assignments: Vec<Assignment>
+}
+
+impl ExpressionOperator for Operator {
+    fn get_binary_precedence(&self) -> Option<(u32, Associativity)> {
+        Some((1, Associativity::Left))
+    }
+
+    fn get_prefix_precedence(&self) -> Option<u32> {
+        Some(2)
+    }
+}
+
+fn round_trip<T : Parsable + ToSource + 'static>(content: &str, options: SourceOptions) -> (String, T) {
+    let item = T::parse(content.to_string(), ParseOptions::default()).unwrap();
+    let source = item.to_source_with_options(options.clone());
+    let parsed = T::parse(source.clone(), ParseOptions::default()).unwrap();
+
+    assert_eq!(parsed.to_source_with_options(options), source);
+
+    (source, parsed)
+}
+
+#[test]
+fn literals_are_written_from_the_attributes() {
+    let (source, program) = round_trip::<Program>("let x = f(1, [2, nil], (y)) -> int;\nlet mut y = [];", SourceOptions::default());
+
+    assert_eq!(source, "let x=f(1,[2,nil],(y))->int;let mut y=[];");
+    assert_eq!(program.statements.len(), 2);
+    assert_eq!(program.statements[1].mutable, "mut");
+
+    match &program.statements[0].value {
+        Value::Call(call) => {
+            assert_eq!(call.arguments.len(), 3);
+            assert!(matches!(&call.arguments[1], Value::List(items) if items.len() == 2));
+            assert!(matches!(&call.arguments[2], Value::Parenthesized(_)));
+            assert_eq!(call.return_type.as_ref().unwrap().name, "int");
+        },
+        _ => panic!("expected a call")
+    }
+}
+
+#[test]
+fn missing_optional_fields_skip_their_prefix() {
+    let (source, program) = round_trip::<Program>("let x = f();", SourceOptions::default());
+
+    assert_eq!(source, "let x=f();");
+    assert!(program.statements[0].mutable.is_empty());
+    assert!(matches!(&program.statements[0].value, Value::Call(call) if call.return_type.is_none()));
+}
+
+#[test]
+fn spacing_can_be_configured() {
+    let options = SourceOptions { spacing: Spacing::Always, ..Default::default() };
+    let (source, _) = round_trip::<Program>("let x=f(1,[2]);", options);
+
+    assert_eq!(source, "let x = f ( 1 , [ 2 ] ) ;");
+}
+
+#[test]
+fn adjacent_operators_are_separated() {
+    let (source, expression) = round_trip::<Expression<Number, Operator>>("1 - -2", SourceOptions::default());
+
+    assert_eq!(source, "1- -2");
+    assert!(matches!(expression, Expression::Binary { right, .. } if matches!(*right, Expression::Prefix { .. })));
+}
+
+#[test]
+fn tuple_fields_are_supported() {
+    let (source, range) = round_trip::<Range>("1 2", SourceOptions::default());
+
+    assert_eq!(source, "1 2");
+    assert_eq!(range.bounds.1.value, "2");
+}
+
+#[test]
+fn items_without_format_can_be_written() {
+    let (source, block) = round_trip::<Block>("{ a = 1, 2; b = 3 }", SourceOptions::default());
+
+    assert_eq!(source, "{a=1,2;b=3}");
+    assert_eq!(block.assignments[1].name.name, "b");
+}
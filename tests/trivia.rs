@@ -0,0 +1,158 @@
+#![allow(dead_code)]
+
+mod common;
+
+use parsable::*;
+use common::with_and_without_memoization;
+
+#[parsable]
+struct Statement {
+    #[parsable(regex=r"[a-z]+", suffix=";")]
+    name: String
+}
+
+#[parsable]
+struct Program {
+    statements: Vec<Statement>
+}
+
+create_token_struct!(QuoteToken, "\"");
+
+#[parsable]
+struct Number {
+    #[parsable(regex=r"\d+")]
+    value: String
+}
+
+#[parsable]
+struct StringLiteral {
+    #[parsable(regex=r#""[^"]*""#)]
+    value: String
+}
+
+// The first variant eats the spaces and comments after the opening quote before failing
+#[parsable]
+enum Value {
+    Split(QuoteToken, Number, QuoteToken),
+    String(StringLiteral)
+}
+
+#[parsable]
+struct Pair {
+    first: Statement,
+    second: Statement
+}
+
+#[parsable]
+struct ExportedPair {
+    pair: Pair,
+    #[parsable(value="!")]
+    mark: String
+}
+
+// The pair is parsed again from the memoization table once `ExportedPair` fails
+#[parsable]
+enum Entry {
+    Exported(ExportedPair),
+    Pair(Pair)
+}
+
+fn options() -> ParseOptions {
+    ParseOptions {
+        comment_start: Some("//"),
+        comments: vec![CommentSyntax::Block("/*", "*/")],
+        collect_comments: true,
+        ..Default::default()
+    }
+}
+
+fn texts<'a>(trivia: impl IntoIterator<Item = &'a Trivia>) -> Vec<&'a str> {
+    trivia.into_iter().map(|trivia| trivia.as_str()).collect()
+}
+
+#[test]
+fn comments_are_collected_with_their_location() {
+    with_and_without_memoization(options(), |options| {
+        let (_, trivia) = Program::parse_with_trivia("// first\na; /* b */ b;\n".to_string(), options).unwrap();
+        let comments : Vec<&Trivia> = trivia.iter().collect();
+
+        assert_eq!(texts(trivia.iter()), ["// first", "/* b */"]);
+        assert_eq!(comments[0].kind, TriviaKind::LineComment);
+        assert_eq!(comments[1].kind, TriviaKind::BlockComment);
+        assert_eq!((comments[1].location.start, comments[1].location.end), (12, 19));
+    });
+}
+
+#[test]
+fn comments_are_not_collected_by_default() {
+    let options = ParseOptions { collect_comments: false, ..options() };
+    let (_, trivia) = Program::parse_with_trivia("// first\na;".to_string(), options).unwrap();
+
+    assert!(trivia.is_empty());
+}
+
+#[test]
+fn whitespace_can_be_collected() {
+    let options = ParseOptions { collect_whitespace: true, ..options() };
+    let (_, trivia) = Program::parse_with_trivia("a;  // end\nb;".to_string(), options).unwrap();
+    let kinds : Vec<TriviaKind> = trivia.iter().map(|trivia| trivia.kind).collect();
+
+    assert_eq!(texts(trivia.iter()), ["  ", "// end", "\n"]);
+    assert_eq!(kinds, [TriviaKind::Whitespace, TriviaKind::LineComment, TriviaKind::Whitespace]);
+    assert_eq!(trivia.comments().count(), 1);
+}
+
+#[test]
+fn leading_comments_are_attached_to_the_following_item() {
+    let content = "// detached\n\n// first\n/* second */\na; // trailing a\nb; /* trailing b */ c;";
+
+    with_and_without_memoization(options(), |options| {
+        let (program, trivia) = Program::parse_with_trivia(content.to_string(), options).unwrap();
+        let [a, b, c] = &program.statements[..] else { panic!("expected 3 statements") };
+
+        assert_eq!(texts(trivia.get_leading(&a.location)), ["// first", "/* second */"]);
+        assert!(trivia.get_leading(&b.location).is_empty());
+        assert!(trivia.get_leading(&c.location).is_empty());
+    });
+}
+
+#[test]
+fn trailing_comments_are_attached_to_the_preceding_item_on_the_same_line() {
+    let content = "a; // trailing a\nb; /* trailing b */ c;\n// next line\n";
+
+    with_and_without_memoization(options(), |options| {
+        let (program, trivia) = Program::parse_with_trivia(content.to_string(), options).unwrap();
+        let [a, b, c] = &program.statements[..] else { panic!("expected 3 statements") };
+
+        assert_eq!(texts(trivia.get_trailing(&a.location)), ["// trailing a"]);
+        assert_eq!(texts(trivia.get_trailing(&b.location)), ["/* trailing b */"]);
+        assert!(trivia.get_trailing(&c.location).is_empty());
+    });
+}
+
+#[test]
+fn comments_inside_an_item_are_found_by_location() {
+    let (program, trivia) = Program::parse_with_trivia("// before\na; /* inside */ b;".to_string(), options()).unwrap();
+
+    assert_eq!(texts(trivia.get_in(&program.location)), ["/* inside */"]);
+}
+
+#[test]
+fn comments_collected_in_a_discarded_branch_are_removed() {
+    with_and_without_memoization(options(), |options| {
+        let (value, trivia) = Value::parse_with_trivia(r#"" /* c */ x""#.to_string(), options).unwrap();
+
+        assert!(matches!(value, Value::String(_)));
+        assert!(trivia.is_empty());
+    });
+}
+
+#[test]
+fn comments_of_memoized_items_are_kept() {
+    with_and_without_memoization(options(), |options| {
+        let (entry, trivia) = Entry::parse_with_trivia("a; /* c */ b;".to_string(), options).unwrap();
+
+        assert!(matches!(entry, Entry::Pair(_)));
+        assert_eq!(texts(trivia.iter()), ["/* c */"]);
+    });
+}
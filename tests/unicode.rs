@@ -0,0 +1,75 @@
+#![allow(dead_code)]
+
+use parsable::*;
+
+#[parsable]
+struct Identifier {
+    #[parsable(regex=r"\w+")]
+    name: String
+}
+
+#[parsable]
+enum Statement {
+    #[parsable(prefix="let")]
+    Let(Identifier),
+    Call(Identifier)
+}
+
+#[parsable]
+struct Program {
+    #[parsable(separator=";")]
+    statements: Vec<Statement>
+}
+
+fn parse(content: &str) -> Program {
+    Program::parse(content.to_string(), ParseOptions::default()).unwrap()
+}
+
+#[test]
+fn keywords_are_not_matched_at_the_start_of_a_unicode_word() {
+    let program = parse("letété; let été");
+
+    assert!(matches!(&program.statements[0], Statement::Call(identifier) if identifier.name == "letété"));
+    assert!(matches!(&program.statements[1], Statement::Let(identifier) if identifier.name == "été"));
+}
+
+#[test]
+fn columns_are_counted_in_characters() {
+    let program = parse("let ça; ñx; y");
+    let Statement::Call(first) = &program.statements[1] else { panic!("expected a call") };
+    let Statement::Call(second) = &program.statements[2] else { panic!("expected a call") };
+
+    assert_eq!((first.location.start, first.location.end), (9, 12));
+    assert_eq!(first.location.get_start_line_col(), (1, 9));
+    assert_eq!(second.location.start, 14);
+    assert_eq!(second.location.get_start_line_col(), (1, 13));
+}
+
+#[test]
+fn errors_after_non_ascii_characters_are_reported_at_their_column() {
+    let error = Program::parse("let ça;
+let é; 🦀".to_string(), ParseOptions::default()).unwrap_err();
+
+    assert_eq!(error.index, 17);
+    assert_eq!(error.file.get_line_col(error.index), Some((2, 8)));
+    assert!(ErrorRenderer::default().render(&error).contains(" --> 2:8"));
+}
+
+#[test]
+fn columns_can_be_counted_in_bytes_or_utf16_code_units() {
+    let file = FileInfo::new("é🦀x\nab".to_string(), String::new(), String::new());
+
+    assert_eq!(file.get_line_col_with_unit(6, ColumnUnit::Byte), Some((1, 7)));
+    assert_eq!(file.get_line_col_with_unit(6, ColumnUnit::Char), Some((1, 3)));
+    assert_eq!(file.get_line_col_with_unit(6, ColumnUnit::Utf16), Some((1, 4)));
+    assert_eq!(file.get_line_col_with_unit(9, ColumnUnit::Utf16), Some((2, 2)));
+}
+
+#[test]
+fn indices_inside_a_character_are_reported_at_its_start() {
+    let file = FileInfo::new("a🦀b".to_string(), String::new(), String::new());
+
+    assert_eq!(file.get_line_col(3), Some((1, 2)));
+    assert_eq!(file.get_line_col(5), Some((1, 3)));
+    assert_eq!(file.get_line_col(7), None);
+}
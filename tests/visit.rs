@@ -0,0 +1,201 @@
+#![allow(dead_code)]
+
+use parsable::*;
+
+#[parsable]
+struct NumberLiteral {
+    #[parsable(regex=r"\d+")]
+    value: String
+}
+
+#[parsable]
+enum Operand {
+    Number(NumberLiteral),
+    #[parsable(brackets="()")]
+    Wrapped(Box<Operation>)
+}
+
+#[parsable]
+enum Operator {
+    Plus = "+",
+    Minus = "-"
+}
+
+#[parsable]
+struct Operation {
+    left: Operand,
+    operator: Operator,
+    right: Operand
+}
+
+// Implements `Parsable` and `Node` manually, but none of the other traits
+#[derive(Debug)]
+struct Ident {
+    name: String,
+    location: ItemLocation
+}
+
+impl Parsable for Ident {
+    fn parse_item(reader: &mut StringReader) -> Option<Self> {
+        let start = reader.get_index();
+        let name = reader.read_regex(r"[a-z]+")?.to_string();
+
+        Some(Self { name, location: reader.get_item_location(start) })
+    }
+
+    fn get_item_name() -> String {
+        "identifier".to_string()
+    }
+
+    fn location(&self) -> &ItemLocation {
+        &self.location
+    }
+}
+
+impl Node for Ident {
+    fn get_node_name(&self) -> String {
+        Self::get_item_name()
+    }
+
+    fn get_node_location(&self) -> Option<&ItemLocation> {
+        Some(&self.location)
+    }
+}
+
+// `Ident` implements neither `ToSource` nor `Format`, which are only implemented if enabled
+#[parsable]
+struct Assignment {
+    name: Ident,
+    #[parsable(prefix="=")]
+    value: Operation
+}
+
+fn parse(string: &str) -> Operation {
+    Operation::parse(string.to_string(), ParseOptions::default()).unwrap()
+}
+
+#[test]
+fn hooks_are_called_for_their_type() {
+    let operation = parse("1 + (2 - 3)");
+    let mut entered = vec![];
+    let mut exited = 0;
+    let mut visitor = Visitor::new();
+
+    visitor
+        .on_enter(|number: &NumberLiteral| {
+            entered.push(number.value.clone());
+            true
+        })
+        .on_exit(|_: &Operation| exited += 1);
+    operation.visit(&mut visitor);
+    drop(visitor);
+
+    assert_eq!(entered, ["1", "2", "3"]);
+    assert_eq!(exited, 2);
+}
+
+#[test]
+fn children_are_skipped_if_enter_returns_false() {
+    let operation = parse("1 + (2 - 3)");
+    let mut strings = vec![];
+    let mut visitor = Visitor::new();
+
+    visitor
+        .on_enter(|operation: &Operation| operation.location.start == 0)
+        .on_enter(|string: &String| {
+            strings.push(string.clone());
+            true
+        });
+    operation.visit(&mut visitor);
+    drop(visitor);
+
+    assert_eq!(strings, ["1"]);
+}
+
+#[test]
+fn hooks_can_modify_items() {
+    let mut operation = parse("01 + (002 - 3)");
+    let mut visitor = VisitorMut::new();
+
+    visitor.on_enter(|number: &mut NumberLiteral| {
+        number.value = number.value.parse::<i32>().unwrap().to_string();
+        true
+    });
+    operation.visit_mut(&mut visitor);
+
+    match (&operation.left, &operation.right) {
+        (Operand::Number(left), Operand::Wrapped(right)) => {
+            assert_eq!(left.value, "1");
+            assert!(matches!(&right.left, Operand::Number(number) if number.value == "2"));
+        },
+        _ => panic!("unexpected operands")
+    }
+}
+
+#[test]
+fn several_hooks_can_be_registered_for_a_type() {
+    let operation = parse("1 + (2 - 3)");
+    let mut first = vec![];
+    let mut second = vec![];
+    let mut visitor = Visitor::new();
+
+    visitor
+        .on_enter(|number: &NumberLiteral| {
+            first.push(number.value.clone());
+            true
+        })
+        .on_enter(|number: &NumberLiteral| {
+            second.push(number.value.clone());
+            true
+        })
+        .on_enter(|_: &Operation| false)
+        .on_enter(|_: &Operation| true);
+    operation.visit(&mut visitor);
+    drop(visitor);
+
+    // The children of the root are skipped, since one of its hooks returned `false`
+    assert!(first.is_empty());
+
+    let mut visitor = Visitor::new();
+
+    visitor
+        .on_enter(|number: &NumberLiteral| {
+            first.push(number.value.clone());
+            true
+        })
+        .on_enter(|number: &NumberLiteral| {
+            second.push(number.value.clone());
+            true
+        });
+    operation.visit(&mut visitor);
+    drop(visitor);
+
+    assert_eq!(first, ["1", "2", "3"]);
+    assert_eq!(second, ["1", "2", "3"]);
+}
+
+#[test]
+fn fields_that_do_not_implement_visit_are_skipped() {
+    let mut assignment = Assignment::parse("a = 1 + 2".to_string(), ParseOptions::default()).unwrap();
+    let mut strings = vec![];
+    let mut visitor = Visitor::new();
+
+    visitor.on_enter(|string: &String| {
+        strings.push(string.clone());
+        true
+    });
+    assignment.visit(&mut visitor);
+    drop(visitor);
+
+    assert_eq!(strings, ["1", "2"]);
+
+    let mut visitor = VisitorMut::new();
+
+    visitor.on_enter(|number: &mut NumberLiteral| {
+        number.value.push('0');
+        true
+    });
+    assignment.visit_mut(&mut visitor);
+
+    assert!(matches!(&assignment.value.left, Operand::Number(number) if number.value == "10"));
+}
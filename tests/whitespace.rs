@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+
+use parsable::*;
+
+#[parsable]
+struct Statement {
+    #[parsable(regex=r"[a-z]+;")]
+    value: String
+}
+
+#[parsable]
+struct Statements {
+    statements: Vec<Statement>
+}
+
+// The line break is part of the token, not whitespace
+#[parsable]
+struct Line {
+    #[parsable(regex=r"[a-z]+\n")]
+    value: String
+}
+
+#[parsable]
+struct Lines {
+    lines: Vec<Line>
+}
+
+#[parsable]
+struct Word {
+    #[parsable(regex=r"[a-z]+")]
+    name: String
+}
+
+#[parsable]
+struct Paragraph {
+    word: Word,
+    blanks: Vec<Newline>
+}
+
+#[parsable(declare_marker="significant_newlines", unset_marker="significant_newlines")]
+struct List {
+    #[parsable(brackets="[]")]
+    items: Vec<Statement>
+}
+
+fn options() -> ParseOptions {
+    ParseOptions {
+        whitespace: Some(" "),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn line_breaks_are_only_skipped_if_configured() {
+    let unix = ParseOptions { whitespace: Some(" \n"), ..Default::default() };
+    let error = Statements::parse("a;\r\nb;".to_string(), unix.clone()).unwrap_err();
+    let statements = Statements::parse("a;\nb;".to_string(), unix).unwrap();
+
+    assert_eq!(error.index, 2);
+    assert_eq!(statements.statements.len(), 2);
+}
+
+// Line breaks are skipped again inside the list even though they are not part of the whitespace set
+#[test]
+fn unset_marker_skips_line_breaks() {
+    let list = List::parse("[a;\r\nb;]".to_string(), options()).unwrap();
+
+    assert_eq!(list.items.len(), 2);
+}
+
+#[test]
+fn error_index_only_backtracks_over_configured_whitespace() {
+    let error = Lines::parse("ab\n+".to_string(), options()).unwrap_err();
+
+    assert_eq!(error.index, 3);
+}
+
+#[test]
+fn location_only_excludes_configured_whitespace() {
+    let lines = Lines::parse("ab\ncd\n".to_string(), options()).unwrap();
+
+    assert_eq!(lines.lines[0].location.end, 3);
+    assert_eq!(lines.lines[1].location.end, 6);
+}
+
+#[test]
+fn newline_matches_the_end_of_the_input_once() {
+    let ended = Paragraph::parse("abc\n".to_string(), options()).unwrap();
+    let unended = Paragraph::parse("abc".to_string(), options()).unwrap();
+    let blank_lines = Paragraph::parse("abc\n \n\n".to_string(), options()).unwrap();
+
+    assert_eq!(ended.blanks.len(), 1);
+    assert_eq!(unended.blanks.len(), 1);
+    assert_eq!(blank_lines.blanks.len(), 1);
+}